@@ -1,5 +1,4 @@
-use std::str::FromStr;
-
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum GameResult {
     Loss,
     Draw,
@@ -7,21 +6,13 @@ enum GameResult {
 }
 
 impl GameResult {
-    fn to_points(&self) -> u32 {
+    fn to_points(self) -> u32 {
         match self {
             GameResult::Win => 6,
             GameResult::Draw => 3,
             GameResult::Loss => 0,
         }
     }
-
-    fn matching_choice(self, opponent: Choice) -> Choice {
-        match self {
-            GameResult::Loss => opponent.losing_choice(),
-            GameResult::Draw => opponent.drawing_choice(),
-            GameResult::Win => opponent.winning_choice(),
-        }
-    }
 }
 
 impl TryFrom<char> for GameResult {
@@ -37,139 +28,248 @@ impl TryFrom<char> for GameResult {
     }
 }
 
-#[derive(PartialEq, Clone, Copy)]
-enum Choice {
-    Rock,
-    Paper,
-    Scissors,
-}
+// The n-th choice of a game, worth n + 1 points
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Choice(usize);
 
 impl Choice {
     fn to_points(self) -> u32 {
-        match self {
-            Choice::Rock => 1,
-            Choice::Paper => 2,
-            Choice::Scissors => 3,
-        }
+        self.0 as u32 + 1
     }
+}
+
+// Which choice beats which, as an explicit table: `beats[a][b]` is true when
+// choice `a` wins against choice `b`. Every choice has to beat exactly half of
+// the others, so no choice is better than the rest.
+struct Rules {
+    beats: Vec<Vec<bool>>,
+}
+
+impl Rules {
+    // Builds the table from the names of the choices, in order, and every pair
+    // of a winner and the choice it beats
+    fn new(choices: &[&str], wins: &[(&str, &str)]) -> Result<Rules, String> {
+        if choices.len().is_multiple_of(2) {
+            return Err(format!(
+                "Expected an odd number of choices, got {}",
+                choices.len()
+            ));
+        }
+        let index = |name: &str| {
+            choices
+                .iter()
+                .position(|&choice| choice == name)
+                .ok_or_else(|| format!("Unknown choice {name:?}"))
+        };
 
-    fn against(self, opponent: Choice) -> GameResult {
-        if self == opponent.losing_choice() {
-            return GameResult::Loss;
+        let mut beats = vec![vec![false; choices.len()]; choices.len()];
+        for &(winner, loser) in wins {
+            let (winner, loser) = (index(winner)?, index(loser)?);
+            if winner == loser || beats[loser][winner] {
+                return Err(format!(
+                    "{} cannot both win and lose against {}",
+                    choices[winner], choices[loser]
+                ));
+            }
+            beats[winner][loser] = true;
         }
-        if self == opponent.winning_choice() {
-            return GameResult::Win;
+
+        for (i, row) in beats.iter().enumerate() {
+            let wins = row.iter().filter(|&&win| win).count();
+            if wins != choices.len() / 2 {
+                return Err(format!(
+                    "{} beats {wins} choices instead of {}",
+                    choices[i],
+                    choices.len() / 2
+                ));
+            }
         }
+        Ok(Rules { beats })
+    }
 
-        GameResult::Draw
+    fn rock_paper_scissors() -> Rules {
+        Rules::new(
+            &["Rock", "Paper", "Scissors"],
+            &[
+                ("Rock", "Scissors"),
+                ("Paper", "Rock"),
+                ("Scissors", "Paper"),
+            ],
+        )
+        .unwrap()
     }
 
-    fn winning_choice(self) -> Self {
-        match self {
-            Choice::Rock => Choice::Paper,
-            Choice::Paper => Choice::Scissors,
-            Choice::Scissors => Choice::Rock,
+    fn rock_paper_scissors_lizard_spock() -> Rules {
+        Rules::new(
+            &["Rock", "Paper", "Scissors", "Spock", "Lizard"],
+            &[
+                ("Scissors", "Paper"),
+                ("Paper", "Rock"),
+                ("Rock", "Lizard"),
+                ("Lizard", "Spock"),
+                ("Spock", "Scissors"),
+                ("Scissors", "Lizard"),
+                ("Lizard", "Paper"),
+                ("Paper", "Spock"),
+                ("Spock", "Rock"),
+                ("Rock", "Scissors"),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn choice(&self, index: usize) -> Result<Choice, String> {
+        if index < self.beats.len() {
+            Ok(Choice(index))
+        } else {
+            Err(format!("There are only {} choices", self.beats.len()))
         }
     }
 
-    fn losing_choice(self) -> Self {
-        match self {
-            Choice::Rock => Choice::Scissors,
-            Choice::Paper => Choice::Rock,
-            Choice::Scissors => Choice::Paper,
+    fn all_choices(&self) -> impl Iterator<Item = Choice> {
+        (0..self.beats.len()).map(Choice)
+    }
+
+    fn outcome(&self, player: Choice, opponent: Choice) -> GameResult {
+        if player == opponent {
+            GameResult::Draw
+        } else if self.beats[player.0][opponent.0] {
+            GameResult::Win
+        } else {
+            GameResult::Loss
         }
     }
 
-    fn drawing_choice(self) -> Self {
-        self
+    fn score(&self, player: Choice, opponent: Choice) -> u32 {
+        player.to_points() + self.outcome(player, opponent).to_points()
     }
-}
 
-impl TryFrom<char> for Choice {
-    type Error = String;
+    // The best scoring choice that leads to the given result
+    fn choice_for(&self, opponent: Choice, result: GameResult) -> Choice {
+        self.all_choices()
+            .filter(|&player| self.outcome(player, opponent) == result)
+            .last()
+            .expect("Every result is possible against every choice")
+    }
 
-    fn try_from(c: char) -> Result<Self, Self::Error> {
-        match c {
-            'A' | 'X' => Ok(Choice::Rock),
-            'B' | 'Y' => Ok(Choice::Paper),
-            'C' | 'Z' => Ok(Choice::Scissors),
-            _ => Err(
-                "Expected A,B,C or X,Y,Z respectively to encode Rock, Paper, Scissors".to_string(),
-            ),
-        }
+    fn best_score(&self, opponent: Choice) -> u32 {
+        self.all_choices()
+            .map(|player| self.score(player, opponent))
+            .max()
+            .unwrap()
     }
 }
 
-struct Round {
-    player: Choice,
-    opponent: Choice,
+// How to read the second column of the strategy guide
+trait Strategy {
+    fn decode(&self, rules: &Rules, opponent: Choice, column: char) -> Result<Choice, String>;
 }
 
-impl Round {
-    fn outcome(self) -> GameResult {
-        self.player.against(self.opponent)
-    }
+// The column is the choice to play, counting letters from `first`
+struct ColumnIsChoice {
+    first: char,
+}
 
-    fn score(self) -> u32 {
-        self.player.to_points() + self.outcome().to_points()
+impl Strategy for ColumnIsChoice {
+    fn decode(&self, rules: &Rules, _opponent: Choice, column: char) -> Result<Choice, String> {
+        let index = (column as usize)
+            .checked_sub(self.first as usize)
+            .ok_or_else(|| {
+                format!(
+                    "Expected a choice from {:?} onwards, got {column:?}",
+                    self.first
+                )
+            })?;
+        rules.choice(index)
     }
 }
 
-impl FromStr for Round {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut chars = s.chars();
-        let (Some(opponent), Some(' '), Some(player), None) = (chars.next(), chars.next(), chars.next(), chars.next()) else {
-            return Err("Expected <opponent> <player>, got {s:?}".to_string());
-        };
+// The column is the result the round needs to end in
+struct ColumnIsResult;
 
-        Ok(Self {
-            player: player.try_into()?,
-            opponent: opponent.try_into()?,
-        })
+impl Strategy for ColumnIsResult {
+    fn decode(&self, rules: &Rules, opponent: Choice, column: char) -> Result<Choice, String> {
+        Ok(rules.choice_for(opponent, GameResult::try_from(column)?))
     }
 }
 
-struct Round2(Round);
+fn opponent_choice(rules: &Rules, column: char) -> Result<Choice, String> {
+    let index = (column as usize)
+        .checked_sub('A' as usize)
+        .ok_or_else(|| format!("Expected a choice from 'A' onwards, got {column:?}"))?;
+    rules.choice(index)
+}
 
-impl FromStr for Round2 {
-    type Err = String;
+// Returns the choices of opponent and player of a round
+fn parse_round(
+    s: &str,
+    rules: &Rules,
+    strategy: &impl Strategy,
+) -> Result<(Choice, Choice), String> {
+    let mut chars = s.chars();
+    let (Some(opponent), Some(' '), Some(player), None) =
+        (chars.next(), chars.next(), chars.next(), chars.next())
+    else {
+        return Err(format!("Expected <opponent> <player>, got {s:?}"));
+    };
+
+    let opponent = opponent_choice(rules, opponent)?;
+    let player = strategy.decode(rules, opponent, player)?;
+    Ok((opponent, player))
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut chars = s.chars();
-        let (Some(opponent), Some(' '), Some(result), None) = (chars.next(), chars.next(), chars.next(), chars.next()) else {
-            return Err("Expected <opponent> <player>, got {s:?}".to_string());
-        };
+fn total_score(input: &str, rules: &Rules, strategy: &impl Strategy) -> u32 {
+    input
+        .lines()
+        .map(|line| {
+            let (opponent, player) = parse_round(line, rules, strategy).unwrap();
+            rules.score(player, opponent)
+        })
+        .sum()
+}
 
-        let opponent = Choice::try_from(opponent)?;
-        let result = GameResult::try_from(result)?;
-        let player = result.matching_choice(opponent);
-        Ok(Self(Round { player, opponent }))
-    }
+// The highest score possible against the opponent's choices, ignoring the player column
+fn best_possible_score(input: &str, rules: &Rules) -> u32 {
+    input
+        .lines()
+        .map(|line| {
+            let opponent = line.chars().next().expect("Rounds must not be empty");
+            rules.best_score(opponent_choice(rules, opponent).unwrap())
+        })
+        .sum()
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut score = 0;
-    for round in input.lines().map(Round::from_str) {
-        score += round.unwrap().score();
-    }
-
-    Some(score)
+    Some(total_score(
+        input,
+        &Rules::rock_paper_scissors(),
+        &ColumnIsChoice { first: 'X' },
+    ))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut score: u32 = 0;
-
-    for round in input.lines().map(Round2::from_str) {
-        score += round.unwrap().0.score();
-    }
-
-    Some(score)
+    Some(total_score(
+        input,
+        &Rules::rock_paper_scissors(),
+        &ColumnIsResult,
+    ))
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 2);
+    // e.g. `cargo solve 02 -- --best --spock`
+    let mut args = pico_args::Arguments::from_env();
+    if args.contains("--best") {
+        let rules = if args.contains("--spock") {
+            Rules::rock_paper_scissors_lizard_spock()
+        } else {
+            Rules::rock_paper_scissors()
+        };
+        println!(
+            "Best possible score: {}",
+            best_possible_score(input, &rules)
+        );
+    }
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -189,4 +289,57 @@ mod tests {
         let input = advent_of_code::read_file("examples", 2);
         assert_eq!(part_two(&input), Some(13));
     }
+
+    #[test]
+    fn test_best_possible_score() {
+        let input = advent_of_code::read_file("examples", 2);
+        assert_eq!(
+            best_possible_score(&input, &Rules::rock_paper_scissors()),
+            23
+        );
+    }
+
+    #[test]
+    fn test_rock_paper_scissors_lizard_spock() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        let (rock, paper, scissors, spock, lizard) =
+            (Choice(0), Choice(1), Choice(2), Choice(3), Choice(4));
+        for (winner, loser) in [
+            (scissors, paper),
+            (paper, rock),
+            (rock, lizard),
+            (lizard, spock),
+            (spock, scissors),
+            (scissors, lizard),
+            (lizard, paper),
+            (paper, spock),
+            (spock, rock),
+            (rock, scissors),
+        ] {
+            assert_eq!(rules.outcome(winner, loser), GameResult::Win);
+            assert_eq!(rules.outcome(loser, winner), GameResult::Loss);
+        }
+
+        // Spock beats rock and scores more than paper
+        assert_eq!(total_score("A Z", &rules, &ColumnIsResult), 10);
+        assert_eq!(best_possible_score("A X", &rules), 10);
+        assert_eq!(
+            total_score("E V", &rules, &ColumnIsChoice { first: 'V' }),
+            1 + 6
+        );
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        // even number of choices
+        assert!(Rules::new(&["A", "B"], &[("A", "B")]).is_err());
+        // unknown choice
+        assert!(Rules::new(&["A", "B", "C"], &[("A", "D")]).is_err());
+        // a choice beating itself
+        assert!(Rules::new(&["A", "B", "C"], &[("A", "A"), ("B", "C"), ("C", "A")]).is_err());
+        // two choices beating each other
+        assert!(Rules::new(&["A", "B", "C"], &[("A", "B"), ("B", "A"), ("C", "A")]).is_err());
+        // one choice beating everything
+        assert!(Rules::new(&["A", "B", "C"], &[("A", "B"), ("A", "C")]).is_err());
+    }
 }
@@ -1,8 +1,9 @@
+use advent_of_code::helpers::LetterSet;
 use item::Item;
 use itertools::Itertools;
-use std::collections::HashSet;
 
 mod item {
+    use advent_of_code::helpers::LetterSet;
 
     #[repr(transparent)]
     #[derive(Clone, Copy, PartialEq, Eq, Hash)]
@@ -20,38 +21,29 @@ mod item {
     }
 
     impl Item {
+        // Letter sets store a-z as 0-25 and A-Z as 26-51, one below the priority
         pub(crate) fn priority(self) -> u32 {
-            match self {
-                Item(b'a'..=b'z') => 1 + (self.0 - b'a') as u32,
-                Item(b'A'..=b'Z') => 27 + (self.0 - b'A') as u32,
-                _ => unreachable!(),
-            }
+            1 + LetterSet::index(self.0).unwrap() as u32
         }
     }
 }
 
+fn common_item(sets: impl IntoIterator<Item = LetterSet>) -> Option<Item> {
+    let common = sets.into_iter().reduce(|a, b| a & b)?;
+    common.first().map(|letter| Item::try_from(letter).unwrap())
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
     let sum: u32 = input
         .lines()
         .map(|line| {
             let (first, second) = line.split_at(line.len() / 2);
-            let first_compartment = first
-                .bytes()
-                .map(Item::try_from)
-                .collect::<Result<HashSet<Item>, _>>()
-                .unwrap();
-            let second_compartment = second
-                .bytes()
-                .map(Item::try_from)
-                .collect::<Result<HashSet<Item>, _>>()
-                .unwrap();
-
-            let common_item = first_compartment
-                .intersection(&second_compartment)
-                .into_iter()
-                .next()
-                .unwrap();
-            common_item.priority()
+            let first_compartment = LetterSet::try_from(first).unwrap();
+            let second_compartment = LetterSet::try_from(second).unwrap();
+
+            common_item([first_compartment, second_compartment])
+                .unwrap()
+                .priority()
         })
         .sum();
 
@@ -59,18 +51,12 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let rucksacks = input.lines().map(|line| {
-        line.bytes()
-            .map(Item::try_from)
-            .collect::<Result<HashSet<_>, _>>()
-    });
+    let rucksacks = input.lines().map(LetterSet::try_from);
 
     let sum = itertools::process_results(rucksacks, |rs| {
         rs.tuples()
             .map(|(a, b, c)| {
-                a.iter()
-                    .copied()
-                    .find(|i| b.contains(i) && c.contains(i))
+                common_item([a, b, c])
                     .map(|i| i.priority())
                     .unwrap_or_default()
             })
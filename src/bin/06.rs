@@ -1,15 +1,67 @@
-use itertools::Itertools;
+use std::collections::VecDeque;
+
+// Keeps track of the last `size` symbols of a stream and how many of them
+// are distinct, so every step is O(1) regardless of the window size.
+struct DistinctWindow {
+    size: usize,
+    counts: Vec<u32>, // occurrences of each symbol of the alphabet within the window
+    symbols: VecDeque<usize>,
+    distinct: usize,
+}
+
+impl DistinctWindow {
+    fn new(size: usize, alphabet_size: usize) -> Self {
+        Self {
+            size,
+            counts: vec![0; alphabet_size],
+            symbols: VecDeque::with_capacity(size + 1),
+            distinct: 0,
+        }
+    }
+
+    // Adds a symbol and returns whether the window is now full of distinct symbols
+    fn push(&mut self, symbol: usize) -> bool {
+        self.symbols.push_back(symbol);
+        self.counts[symbol] += 1;
+        if self.counts[symbol] == 1 {
+            self.distinct += 1;
+        }
+
+        if self.symbols.len() > self.size {
+            let dropped = self.symbols.pop_front().unwrap();
+            self.counts[dropped] -= 1;
+            if self.counts[dropped] == 0 {
+                self.distinct -= 1;
+            }
+        }
+
+        self.symbols.len() == self.size && self.distinct == self.size
+    }
+}
+
+// Yields the number of symbols consumed at every point where the last
+// `window_size` symbols were all different. Works on any stream of symbols,
+// e.g. `reader.bytes()`, as long as they are below `alphabet_size`.
+fn markers<S: Into<usize>>(
+    symbols: impl IntoIterator<Item = S>,
+    window_size: usize,
+    alphabet_size: usize,
+) -> impl Iterator<Item = usize> {
+    let mut window = DistinctWindow::new(window_size, alphabet_size);
+    symbols
+        .into_iter()
+        .enumerate()
+        .filter_map(move |(i, symbol)| window.push(symbol.into()).then_some(i + 1))
+}
 
 fn find_signal(input: &str, window_size: usize) -> Option<u32> {
-    input
-    .as_bytes()
-    .windows(window_size)
-    .position(|window| window.iter().unique().count() == window_size)
-    .map(|pos| (pos + window_size) as u32)
+    markers(input.bytes(), window_size, 256)
+        .next()
+        .map(|pos| pos as u32)
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-  find_signal(input, 4)
+    find_signal(input, 4)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
@@ -44,6 +96,18 @@ mod tests {
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 6);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(19));
+    }
+
+    #[test]
+    fn test_all_markers() {
+        use std::io::Read;
+
+        let reader = "mjqjpqmgbl".as_bytes();
+        let found: Vec<_> = markers(reader.bytes().map(Result::unwrap), 4, 256).collect();
+        assert_eq!(found, vec![7, 8, 9, 10]);
+
+        let found: Vec<_> = markers([0_u8, 1, 0, 2, 1, 1], 2, 3).collect();
+        assert_eq!(found, vec![2, 3, 4, 5]);
     }
 }
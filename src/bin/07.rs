@@ -1,13 +1,181 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+const DISK_SIZE: u32 = 70_000_000;
+const REQUIRED_SPACE: u32 = 30_000_000;
+
+type NodeId = usize;
+
+enum NodeKind {
+    Dir { children: Vec<NodeId> },
+    File { size: u32 },
+}
+
+struct Node {
+    name: String,
+    parent: Option<NodeId>,
+    kind: NodeKind,
+}
+
+// Directory tree with all nodes stored in one arena. Nodes are only ever
+// appended, so a node always has a larger id than its parent.
+struct FileSystem {
+    nodes: Vec<Node>,
+}
+
+impl FileSystem {
+    const ROOT: NodeId = 0;
+
+    fn new() -> Self {
+        Self {
+            nodes: vec![Node {
+                name: "/".to_string(),
+                parent: None,
+                kind: NodeKind::Dir { children: vec![] },
+            }],
+        }
+    }
+
+    fn child(&self, dir: NodeId, name: &str) -> Option<NodeId> {
+        match &self.nodes[dir].kind {
+            NodeKind::Dir { children } => children
+                .iter()
+                .copied()
+                .find(|&child| self.nodes[child].name == name),
+            NodeKind::File { .. } => None,
+        }
+    }
+
+    // Adds a node to a directory, unless it has been listed before
+    fn add(&mut self, dir: NodeId, name: &str, kind: NodeKind) -> Result<NodeId, String> {
+        if let Some(existing) = self.child(dir, name) {
+            return Ok(existing);
+        }
+
+        let id = self.nodes.len();
+        match &mut self.nodes[dir].kind {
+            NodeKind::Dir { children } => children.push(id),
+            NodeKind::File { .. } => return Err(format!("Cannot add {name:?} to a file")),
+        }
+        self.nodes.push(Node {
+            name: name.to_string(),
+            parent: Some(dir),
+            kind,
+        });
+        Ok(id)
+    }
+
+    // Total size of every node, indexed by node id
+    fn sizes(&self) -> Vec<u32> {
+        let mut sizes: Vec<u32> = self
+            .nodes
+            .iter()
+            .map(|node| match node.kind {
+                NodeKind::File { size } => size,
+                NodeKind::Dir { .. } => 0,
+            })
+            .collect();
+
+        // Children come after their parents, so walking backwards visits
+        // every node only after all of its descendants have been added up
+        for id in (1..self.nodes.len()).rev() {
+            let parent = self.nodes[id].parent.unwrap();
+            sizes[parent] += sizes[id];
+        }
+
+        sizes
+    }
+
+    fn dir_sizes(&self) -> impl Iterator<Item = u32> + '_ {
+        self.sizes()
+            .into_iter()
+            .zip(self.nodes.iter())
+            .filter(|(_, node)| matches!(node.kind, NodeKind::Dir { .. }))
+            .map(|(size, _)| size)
+    }
+
+    fn fmt_node(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        id: NodeId,
+        depth: usize,
+    ) -> std::fmt::Result {
+        let node = &self.nodes[id];
+        let indent = "  ".repeat(depth);
+        match &node.kind {
+            NodeKind::File { size } => writeln!(f, "{indent}- {} (file, size={size})", node.name),
+            NodeKind::Dir { children } => {
+                writeln!(f, "{indent}- {} (dir)", node.name)?;
+                children
+                    .iter()
+                    .try_for_each(|&child| self.fmt_node(f, child, depth + 1))
+            }
+        }
+    }
+}
+
+impl FromStr for FileSystem {
+    type Err = String;
+
+    // Replays a terminal transcript of `cd` and `ls` commands
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fs = FileSystem::new();
+        let mut cwd = FileSystem::ROOT;
+
+        for line in s.lines() {
+            match line.split_whitespace().collect::<Vec<_>>()[..] {
+                ["$", "cd", "/"] => cwd = FileSystem::ROOT,
+                ["$", "cd", ".."] => {
+                    cwd = fs.nodes[cwd]
+                        .parent
+                        .ok_or("Cannot move out of the root directory")?
+                }
+                ["$", "cd", name] => {
+                    cwd = fs
+                        .child(cwd, name)
+                        .ok_or_else(|| format!("No directory {name:?} has been listed"))?
+                }
+                ["$", "ls"] => {}
+                ["dir", name] => {
+                    fs.add(cwd, name, NodeKind::Dir { children: vec![] })?;
+                }
+                [size, name] => {
+                    let size = size
+                        .parse()
+                        .map_err(|_| format!("Expected <size> <name>, got {line:?}"))?;
+                    fs.add(cwd, name, NodeKind::File { size })?;
+                }
+                _ => return Err(format!("Unexpected line {line:?}")),
+            }
+        }
+
+        Ok(fs)
+    }
+}
+
+impl Display for FileSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_node(f, FileSystem::ROOT, 0)
+    }
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    None
+    let fs = FileSystem::from_str(input).unwrap();
+    Some(fs.dir_sizes().filter(|&size| size <= 100_000).sum())
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    None
+    let fs = FileSystem::from_str(input).unwrap();
+    let used = fs.sizes()[FileSystem::ROOT];
+    let to_free = (used + REQUIRED_SPACE).saturating_sub(DISK_SIZE);
+    fs.dir_sizes().filter(|&size| size >= to_free).min()
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 7);
+    if pico_args::Arguments::from_env().contains("--tree") {
+        print!("{}", FileSystem::from_str(input).unwrap());
+    }
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -19,12 +187,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 7);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input), Some(95437));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 7);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(24933642));
     }
 }
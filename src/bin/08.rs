@@ -1,13 +1,100 @@
+use advent_of_code::helpers::{Grid, Point2};
+
+const HEATMAP_SHADES: &[u8] = b" .:-=+*#%@";
+
+struct Survey {
+    visible: Grid<bool>,
+    scenic_scores: Grid<u32>,
+}
+
+// Every row and column in both directions, each ordered from the edge inwards,
+// so trees look back along the line towards its start.
+fn sightlines(width: usize, height: usize) -> impl Iterator<Item = Vec<Point2>> {
+    let rows =
+        (0..height).map(move |y| (0..width).map(|x| Point2::from((x, y))).collect::<Vec<_>>());
+    let columns = (0..width).map(move |x| {
+        (0..height)
+            .map(|y| Point2::from((x, y)))
+            .collect::<Vec<_>>()
+    });
+    rows.chain(columns).flat_map(|line| {
+        let reversed = line.iter().rev().copied().collect();
+        [line, reversed]
+    })
+}
+
+// Scans every sightline once, keeping a stack of the trees that are still
+// visible from the current position, tallest at the bottom. Every tree is
+// pushed and popped at most once per line, so the survey is O(rows * cols).
+fn survey(heights: &Grid<u8>) -> Survey {
+    let mut visible = heights.map(|_| false);
+    let mut scenic_scores = heights.map(|_| 1);
+
+    for line in sightlines(heights.width, heights.height) {
+        let mut stack: Vec<(u8, usize)> = vec![];
+        for (i, &point) in line.iter().enumerate() {
+            let height = heights[point];
+            while stack.last().is_some_and(|&(h, _)| h < height) {
+                stack.pop();
+            }
+
+            let viewing_distance = match stack.last() {
+                Some(&(_, blocker)) => i - blocker,
+                None => {
+                    visible[point] = true;
+                    i
+                }
+            };
+            scenic_scores[point] *= viewing_distance as u32;
+            stack.push((height, i));
+        }
+    }
+
+    Survey {
+        visible,
+        scenic_scores,
+    }
+}
+
+fn parse(input: &str) -> Grid<u8> {
+    Grid::parse(input, |b| match b {
+        b'0'..=b'9' => Ok(b - b'0'),
+        _ => Err(format!("Expected a digit, got {:?}", b as char)),
+    })
+    .unwrap()
+}
+
+// Renders scenic scores relative to the best one, darkest is best
+fn render_heatmap(scenic_scores: &Grid<u32>) -> String {
+    let max = scenic_scores
+        .iter()
+        .map(|(_, &s)| s)
+        .max()
+        .unwrap_or(0)
+        .max(1) as u64;
+    scenic_scores
+        .map(|&score| {
+            let shade = score as u64 * (HEATMAP_SHADES.len() as u64 - 1) / max;
+            HEATMAP_SHADES[shade as usize] as char
+        })
+        .to_string()
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    None
+    let survey = survey(&parse(input));
+    Some(survey.visible.iter().filter(|(_, &v)| v).count() as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    None
+    let survey = survey(&parse(input));
+    survey.scenic_scores.iter().map(|(_, &s)| s).max()
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 8);
+    if pico_args::Arguments::from_env().contains("--heatmap") {
+        println!("{}", render_heatmap(&survey(&parse(input)).scenic_scores));
+    }
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -19,12 +106,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 8);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input), Some(21));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 8);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(8));
     }
 }
@@ -1,13 +1,124 @@
+use advent_of_code::helpers::{Dir4, Point2};
+use std::collections::HashSet;
+use std::fmt::Display;
+
+struct Motion {
+    dir: Dir4,
+    steps: u32,
+}
+
+struct Rope {
+    knots: Vec<Point2>,
+    tail_trail: HashSet<Point2>,
+}
+
+impl Rope {
+    fn new(knot_count: usize) -> Self {
+        assert!(knot_count > 0, "A rope needs at least one knot");
+        Self {
+            knots: vec![Point2::ORIGIN; knot_count],
+            tail_trail: HashSet::from([Point2::ORIGIN]),
+        }
+    }
+
+    fn apply(&mut self, motion: &Motion) {
+        for _ in 0..motion.steps {
+            self.step(motion.dir);
+        }
+    }
+
+    // Moves the head one step, every other knot follows the one in front of it
+    // once they are no longer touching. Stops early once a knot stays in place.
+    fn step(&mut self, dir: Dir4) {
+        self.knots[0] += dir;
+        for i in 1..self.knots.len() {
+            let delta = self.knots[i - 1] - self.knots[i];
+            if delta.x.abs() <= 1 && delta.y.abs() <= 1 {
+                return;
+            }
+            self.knots[i] += delta.signum();
+        }
+        self.tail_trail.insert(*self.knots.last().unwrap());
+    }
+}
+
+// Draws the knots on top of the tail trail, like the drawings in the puzzle
+impl Display for Rope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let points = || self.knots.iter().chain(self.tail_trail.iter());
+        let min_x = points().map(|p| p.x).min().unwrap();
+        let max_x = points().map(|p| p.x).max().unwrap();
+        let min_y = points().map(|p| p.y).min().unwrap();
+        let max_y = points().map(|p| p.y).max().unwrap();
+
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let point = Point2::new(x, y);
+                let c = match self.knots.iter().position(|&knot| knot == point) {
+                    Some(0) => 'H',
+                    Some(i) => char::from_digit(i as u32 % 10, 10).unwrap(),
+                    None if point == Point2::ORIGIN => 's',
+                    None if self.tail_trail.contains(&point) => '#',
+                    None => '.',
+                };
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn motions(input: &str) -> impl Iterator<Item = Motion> + '_ {
+    input.lines().map(|line| {
+        let (dir, steps) = line
+            .split_once(' ')
+            .expect("Each motion must be a direction and a number of steps");
+        Motion {
+            dir: Dir4::try_from(dir.chars().next().unwrap()).unwrap(),
+            steps: steps.parse().expect("Steps must be a u32"),
+        }
+    })
+}
+
+// Runs all motions on a rope of `knot_count` knots. `on_motion` is called
+// after every motion, e.g. to render intermediate frames.
+fn simulate(input: &str, knot_count: usize, mut on_motion: impl FnMut(&Rope)) -> Rope {
+    let mut rope = Rope::new(knot_count);
+    for motion in motions(input) {
+        rope.apply(&motion);
+        on_motion(&rope);
+    }
+    rope
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    None
+    Some(simulate(input, 2, |_| {}).tail_trail.len() as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    None
+    Some(simulate(input, 10, |_| {}).tail_trail.len() as u32)
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 9);
+
+    // e.g. `cargo solve 09 -- --knots 3 --frames`
+    let mut args = pico_args::Arguments::from_env();
+    let frames = args.contains("--frames");
+    let trail = args.contains("--trail");
+    if frames || trail {
+        let knot_count = args.opt_value_from_str("--knots").unwrap().unwrap_or(10);
+        let rope = simulate(input, knot_count, |rope| {
+            if frames {
+                println!("{rope}");
+            }
+        });
+        if trail && !frames {
+            println!("{rope}");
+        }
+    }
+
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -19,12 +130,15 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 9);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input), Some(13));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 9);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(1));
+
+        let input = "R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20";
+        assert_eq!(part_two(input), Some(36));
     }
 }
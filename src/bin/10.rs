@@ -1,13 +1,139 @@
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+use std::str::FromStr;
+
+const CRT_WIDTH: usize = 40;
+const CRT_HEIGHT: usize = 6;
+const SAMPLED_CYCLES: [usize; 6] = [20, 60, 100, 140, 180, 220];
+
+// The 4x6 font used by all puzzles that draw letters, each letter is
+// followed by an empty column on the CRT
+const GLYPH_WIDTH: usize = 5;
+const FONT: [(char, &str); 17] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('I', ".###..#...#...#...#..###"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+#[derive(Clone, Copy)]
+enum Instruction {
+    Noop,
+    Addx(i32),
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+impl Instruction {
+    fn cycles(self) -> usize {
+        match self {
+            Instruction::Noop => 1,
+            Instruction::Addx(_) => 2,
+        }
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(' ') {
+            None if s == "noop" => Ok(Instruction::Noop),
+            Some(("addx", value)) => value
+                .parse()
+                .map(Instruction::Addx)
+                .map_err(|_| format!("Expected addx <i32>, got {s:?}")),
+            _ => Err(format!("Expected noop or addx <i32>, got {s:?}")),
+        }
+    }
+}
+
+// The value of the X register *during* every cycle, starting with cycle 1.
+// `addx` only takes effect once both of its cycles have passed.
+fn register_values(input: &str) -> impl Iterator<Item = i32> + '_ {
+    input
+        .lines()
+        .map(|line| Instruction::from_str(line).unwrap())
+        .scan(1, |x, instruction| {
+            let during = *x;
+            if let Instruction::Addx(value) = instruction {
+                *x += value;
+            }
+            Some(std::iter::repeat_n(during, instruction.cycles()))
+        })
+        .flatten()
+}
+
+// Draws a pixel whenever the 3 pixel wide sprite centered on X overlaps
+// the position the CRT is currently drawing
+fn render_crt(input: &str) -> String {
+    let pixels: Vec<char> = register_values(input)
+        .take(CRT_WIDTH * CRT_HEIGHT)
+        .enumerate()
+        .map(|(i, x)| {
+            let column = (i % CRT_WIDTH) as i32;
+            if (column - x).abs() <= 1 {
+                '#'
+            } else {
+                '.'
+            }
+        })
+        .collect();
+
+    pixels
+        .chunks(CRT_WIDTH)
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Reads the letters on the CRT, `None` if any of them is not in the font
+fn ocr(screen: &str) -> Option<String> {
+    let rows: Vec<&str> = screen.lines().collect();
+    if rows.len() != CRT_HEIGHT {
+        return None;
+    }
+
+    let width = rows.iter().map(|row| row.len()).min()?;
+    (0..width)
+        .step_by(GLYPH_WIDTH)
+        .map(|start| {
+            let glyph: String = rows.iter().map(|row| &row[start..start + 4]).collect();
+            FONT.iter()
+                .find(|(_, pattern)| *pattern == glyph)
+                .map(|&(letter, _)| letter)
+        })
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Option<i32> {
+    let signal_strength = register_values(input)
+        .enumerate()
+        .map(|(i, x)| (i + 1, x))
+        .filter(|(cycle, _)| SAMPLED_CYCLES.contains(cycle))
+        .map(|(cycle, x)| cycle as i32 * x)
+        .sum();
+    Some(signal_strength)
+}
+
+pub fn part_two(input: &str) -> Option<String> {
+    ocr(&render_crt(input))
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 10);
+    if pico_args::Arguments::from_env().contains("--crt") {
+        println!("{}", render_crt(input));
+    }
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -19,12 +145,33 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 10);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input), Some(13140));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 10);
+        // the example draws a test pattern rather than letters
         assert_eq!(part_two(&input), None);
+        assert_eq!(
+            render_crt(&input),
+            "##..##..##..##..##..##..##..##..##..##..\n\
+             ###...###...###...###...###...###...###.\n\
+             ####....####....####....####....####....\n\
+             #####.....#####.....#####.....#####.....\n\
+             ######......######......######......####\n\
+             #######.......#######.......#######....."
+        );
+    }
+
+    #[test]
+    fn test_ocr() {
+        let screen = "###..####.####.#..#.####.####.#..#..##..\n\
+                      #..#....#.#....#.#..#....#....#..#.#..#.\n\
+                      #..#...#..###..##...###..###..####.#....\n\
+                      ###...#...#....#.#..#....#....#..#.#.##.\n\
+                      #.#..#....#....#.#..#....#....#..#.#..#.\n\
+                      #..#.####.####.#..#.#....####.#..#..###.";
+        assert_eq!(ocr(screen), Some("RZEKFEHG".to_string()));
     }
 }
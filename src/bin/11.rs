@@ -1,9 +1,144 @@
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+use advent_of_code::helpers::lcm;
+use std::str::FromStr;
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Add(u64),
+    Multiply(u64),
+    Square,
+}
+
+impl Operation {
+    fn apply(self, old: u64) -> u64 {
+        match self {
+            Operation::Add(n) => old + n,
+            Operation::Multiply(n) => old * n,
+            Operation::Square => old * old,
+        }
+    }
+}
+
+impl FromStr for Operation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |n: &str| {
+            n.parse()
+                .map_err(|_| format!("Expected a number, got {n:?}"))
+        };
+        match s.split_whitespace().collect::<Vec<_>>()[..] {
+            ["new", "=", "old", "*", "old"] => Ok(Operation::Square),
+            ["new", "=", "old", "*", n] => Ok(Operation::Multiply(parse(n)?)),
+            ["new", "=", "old", "+", n] => Ok(Operation::Add(parse(n)?)),
+            _ => Err(format!("Unsupported operation {s:?}")),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Monkey {
+    items: Vec<u64>,
+    operation: Operation,
+    divisor: u64,
+    if_true: usize,  // monkey to throw to if the worry level is divisible
+    if_false: usize, // monkey to throw to otherwise
+    inspections: u64,
+}
+
+impl FromStr for Monkey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().skip(1).map(|line| {
+            line.split_once(": ")
+                .map(|(_, value)| value)
+                .ok_or_else(|| format!("Expected <field>: <value>, got {line:?}"))
+        });
+        let mut next = || {
+            lines
+                .next()
+                .unwrap_or(Err("Monkey is incomplete".to_string()))
+        };
+        let last_number = |s: &str| {
+            s.rsplit(' ')
+                .next()
+                .and_then(|n| n.parse().ok())
+                .ok_or_else(|| format!("Expected {s:?} to end with a number"))
+        };
+
+        let items = next()?
+            .split(", ")
+            .map(|item| item.parse().map_err(|_| format!("Invalid item {item:?}")))
+            .collect::<Result<_, _>>()?;
+        let operation = next()?.parse()?;
+        let divisor = last_number(next()?)?;
+        let if_true = last_number(next()?)? as usize;
+        let if_false = last_number(next()?)? as usize;
+
+        Ok(Self {
+            items,
+            operation,
+            divisor,
+            if_true,
+            if_false,
+            inspections: 0,
+        })
+    }
+}
+
+fn parse(input: &str) -> Vec<Monkey> {
+    input
+        .split("\n\n")
+        .map(|block| Monkey::from_str(block).unwrap())
+        .collect()
+}
+
+// Plays the given number of rounds and multiplies the two highest inspection counts.
+// Without relief, worry levels are kept modulo the lcm of all divisors, which leaves
+// the outcome of every divisibility test unchanged.
+fn monkey_business(mut monkeys: Vec<Monkey>, rounds: usize, relief: bool) -> u64 {
+    let modulus = monkeys.iter().map(|m| m.divisor).fold(1, lcm);
+
+    for _ in 0..rounds {
+        for i in 0..monkeys.len() {
+            let items = std::mem::take(&mut monkeys[i].items);
+            monkeys[i].inspections += items.len() as u64;
+            let Monkey {
+                operation,
+                divisor,
+                if_true,
+                if_false,
+                ..
+            } = monkeys[i];
+
+            for item in items {
+                let mut worry = operation.apply(item);
+                if relief {
+                    worry /= 3;
+                } else {
+                    worry %= modulus;
+                }
+                let target = if worry % divisor == 0 {
+                    if_true
+                } else {
+                    if_false
+                };
+                monkeys[target].items.push(worry);
+            }
+        }
+    }
+
+    let mut inspections: Vec<u64> = monkeys.iter().map(|m| m.inspections).collect();
+    inspections.sort_by_key(|&v| std::cmp::Reverse(v));
+    inspections.iter().take(2).product()
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    Some(monkey_business(parse(input), 20, true))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+pub fn part_two(input: &str) -> Option<u64> {
+    Some(monkey_business(parse(input), 10_000, false))
 }
 
 fn main() {
@@ -19,12 +154,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 11);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input), Some(10605));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 11);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(2713310158));
     }
 }
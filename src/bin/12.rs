@@ -1,13 +1,111 @@
+use advent_of_code::helpers::{Dir4, Grid, Point2};
+use std::collections::VecDeque;
+
+struct Heightmap {
+    elevations: Grid<u8>,
+    start: Point2,
+    end: Point2,
+}
+
+impl Heightmap {
+    fn parse(input: &str) -> Self {
+        let cells = Grid::parse(input, |b| match b {
+            b'a'..=b'z' | b'S' | b'E' => Ok(b),
+            _ => Err(format!("Unexpected elevation {:?}", b as char)),
+        })
+        .unwrap();
+        let start = cells
+            .position(|&b| b == b'S')
+            .expect("Heightmap has a start");
+        let end = cells
+            .position(|&b| b == b'E')
+            .expect("Heightmap has an end");
+        let elevations = cells.map(|&b| match b {
+            b'S' => b'a',
+            b'E' => b'z',
+            _ => b,
+        });
+
+        Self {
+            elevations,
+            start,
+            end,
+        }
+    }
+
+    // Breadth-first search backwards from the end, so a single search finds the
+    // closest of many possible starting points. Returns the route from the
+    // reached starting point to the end.
+    fn route_to_end(&self, is_start: impl Fn(Point2) -> bool) -> Option<Vec<Point2>> {
+        // the next step towards the end, for every point reached so far
+        let mut towards_end: Grid<Option<Point2>> = self.elevations.map(|_| None);
+        let mut queue = VecDeque::from([self.end]);
+        towards_end[self.end] = Some(self.end);
+
+        while let Some(point) = queue.pop_front() {
+            if is_start(point) {
+                let mut route = vec![point];
+                while *route.last().unwrap() != self.end {
+                    route.push(towards_end[*route.last().unwrap()].unwrap());
+                }
+                return Some(route);
+            }
+
+            for previous in point.neighbours4() {
+                let reachable = self
+                    .elevations
+                    .get(previous)
+                    .is_some_and(|&e| self.elevations[point] <= e + 1);
+                if reachable && towards_end[previous].is_none() {
+                    towards_end[previous] = Some(point);
+                    queue.push_back(previous);
+                }
+            }
+        }
+
+        None
+    }
+
+    // Draws the route onto the map the way the puzzle does
+    fn render_route(&self, route: &[Point2]) -> String {
+        let mut overlay = self.elevations.map(|_| '.');
+        for step in route.windows(2) {
+            let dir = Dir4::ALL
+                .into_iter()
+                .find(|&dir| step[0] + dir == step[1])
+                .unwrap();
+            overlay[step[0]] = match dir {
+                Dir4::Right => '>',
+                Dir4::Down => 'v',
+                Dir4::Left => '<',
+                Dir4::Up => '^',
+            };
+        }
+        overlay[self.end] = 'E';
+        overlay.to_string()
+    }
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    None
+    let heightmap = Heightmap::parse(input);
+    let route = heightmap.route_to_end(|p| p == heightmap.start)?;
+    Some(route.len() as u32 - 1)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    None
+    let heightmap = Heightmap::parse(input);
+    let route = heightmap.route_to_end(|p| heightmap.elevations[p] == b'a')?;
+    Some(route.len() as u32 - 1)
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 12);
+    if pico_args::Arguments::from_env().contains("--path") {
+        let heightmap = Heightmap::parse(input);
+        if let Some(route) = heightmap.route_to_end(|p| p == heightmap.start) {
+            println!("{}", heightmap.render_route(&route));
+        }
+    }
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -19,12 +117,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 12);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input), Some(31));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 12);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(29));
     }
 }
@@ -1,9 +1,144 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Packet {
+    Integer(u32),
+    List(Vec<Packet>),
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Integer(a), Packet::Integer(b)) => a.cmp(b),
+            (Packet::List(a), Packet::List(b)) => a.cmp(b),
+            (Packet::Integer(a), Packet::List(b)) => [Packet::Integer(*a)][..].cmp(b),
+            (Packet::List(a), Packet::Integer(b)) => a[..].cmp(&[Packet::Integer(*b)]),
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Recursive descent parser over the bytes of a single packet
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), String> {
+        match self.peek() {
+            Some(b) if b == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            found => Err(format!(
+                "Expected {:?} at position {}, found {:?}",
+                expected as char,
+                self.pos,
+                found.map(|b| b as char)
+            )),
+        }
+    }
+
+    fn packet(&mut self) -> Result<Packet, String> {
+        match self.peek() {
+            Some(b'[') => self.list(),
+            Some(b'0'..=b'9') => self.integer(),
+            found => Err(format!(
+                "Expected a list or an integer at position {}, found {:?}",
+                self.pos,
+                found.map(|b| b as char)
+            )),
+        }
+    }
+
+    fn list(&mut self) -> Result<Packet, String> {
+        self.expect(b'[')?;
+        let mut items = vec![];
+        if self.peek() != Some(b']') {
+            items.push(self.packet()?);
+            while self.peek() == Some(b',') {
+                self.pos += 1;
+                items.push(self.packet()?);
+            }
+        }
+        self.expect(b']')?;
+        Ok(Packet::List(items))
+    }
+
+    fn integer(&mut self) -> Result<Packet, String> {
+        let mut value = 0;
+        while let Some(digit @ b'0'..=b'9') = self.peek() {
+            value = value * 10 + (digit - b'0') as u32;
+            self.pos += 1;
+        }
+        Ok(Packet::Integer(value))
+    }
+}
+
+impl FromStr for Packet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            bytes: s.as_bytes(),
+            pos: 0,
+        };
+        let packet = parser.packet()?;
+        if parser.pos != s.len() {
+            return Err(format!("Unexpected trailing input in {s:?}"));
+        }
+        Ok(packet)
+    }
+}
+
+fn packets(input: &str) -> impl Iterator<Item = Packet> + '_ {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| Packet::from_str(line).unwrap())
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    None
+    let mut packets = packets(input);
+    let mut sum = 0;
+    let mut index = 1;
+    while let (Some(left), Some(right)) = (packets.next(), packets.next()) {
+        if left < right {
+            sum += index;
+        }
+        index += 1;
+    }
+    Some(sum)
 }
 
+// The position of a divider in the sorted packets only depends on how
+// many packets are smaller than it, so there is no need to sort
 pub fn part_two(input: &str) -> Option<u32> {
-    None
+    let first_divider = Packet::from_str("[[2]]").unwrap();
+    let second_divider = Packet::from_str("[[6]]").unwrap();
+
+    let (mut first_index, mut second_index) = (1, 2);
+    for packet in packets(input) {
+        if packet < first_divider {
+            first_index += 1;
+            second_index += 1;
+        } else if packet < second_divider {
+            second_index += 1;
+        }
+    }
+
+    Some(first_index * second_index)
 }
 
 fn main() {
@@ -19,12 +154,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 13);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input), Some(13));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 13);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(140));
     }
 }
@@ -1,13 +1,161 @@
+use advent_of_code::helpers::{Grid, Point2};
+use std::fmt::Display;
+
+const SOURCE: Point2 = Point2::new(500, 0);
+const FALL_DIRECTIONS: [Point2; 3] = [Point2::new(0, 1), Point2::new(-1, 1), Point2::new(1, 1)];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
+
+struct Cave {
+    tiles: Grid<Tile>,
+    offset_x: i32, // cave x coordinate of the first column of `tiles`
+    floor_y: i32,  // two below the lowest rock
+    settled: u32,
+}
+
+impl Cave {
+    fn parse(input: &str) -> Self {
+        let paths: Vec<Vec<Point2>> = input
+            .lines()
+            .map(|line| {
+                line.split(" -> ")
+                    .map(|point| {
+                        let (x, y) = point
+                            .split_once(',')
+                            .expect("Points must be two numbers separated by a comma");
+                        Point2::new(x.parse().unwrap(), y.parse().unwrap())
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // Sand piles up in a triangle below the source, so it can never get
+        // further sideways than the floor is deep
+        let floor_y = paths.iter().flatten().map(|p| p.y).max().unwrap_or(0) + 2;
+        let offset_x = SOURCE.x - floor_y - 1;
+        let mut cave = Self {
+            tiles: Grid::new(2 * floor_y as usize + 3, floor_y as usize + 1, Tile::Air),
+            offset_x,
+            floor_y,
+            settled: 0,
+        };
+
+        for path in paths {
+            for segment in path.windows(2) {
+                let step = (segment[1] - segment[0]).signum();
+                let mut point = segment[0];
+                cave.add_rock(point);
+                while point != segment[1] {
+                    point += step;
+                    cave.add_rock(point);
+                }
+            }
+        }
+
+        cave
+    }
+
+    fn local(&self, point: Point2) -> Point2 {
+        Point2::new(point.x - self.offset_x, point.y)
+    }
+
+    fn get(&self, point: Point2) -> Tile {
+        self.tiles
+            .get(self.local(point))
+            .copied()
+            .unwrap_or(Tile::Air)
+    }
+
+    // Rock further sideways than sand can ever get is left out
+    fn add_rock(&mut self, point: Point2) {
+        let local = self.local(point);
+        if let Some(tile) = self.tiles.get_mut(local) {
+            *tile = Tile::Rock;
+        }
+    }
+
+    fn set(&mut self, point: Point2, tile: Tile) {
+        let local = self.local(point);
+        self.tiles[local] = tile;
+    }
+
+    // Pours sand until it either falls into the abyss or, with a floor, until
+    // the source is blocked. Returns the number of grains at rest.
+    //
+    // Every grain follows the path of the previous one until that grain's
+    // resting place, so instead of dropping each grain from the source, the
+    // next grain starts where the previous one was just before coming to rest.
+    fn pour(&mut self, with_floor: bool) -> u32 {
+        let mut path = vec![SOURCE];
+
+        while let Some(&grain) = path.last() {
+            let next = FALL_DIRECTIONS
+                .iter()
+                .map(|&dir| grain + dir)
+                .find(|&p| p.y < self.floor_y && self.get(p) == Tile::Air);
+
+            match next {
+                Some(next) if !with_floor && next.y >= self.floor_y - 1 => break,
+                Some(next) => path.push(next),
+                None => {
+                    self.set(grain, Tile::Sand);
+                    self.settled += 1;
+                    path.pop();
+                }
+            }
+        }
+
+        self.settled
+    }
+}
+
+// Draws the part of the cave that contains rock or sand
+impl Display for Cave {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let used = || self.tiles.iter().filter(|(_, &tile)| tile != Tile::Air);
+        let min_x = used().map(|(p, _)| p.x).min().unwrap_or(0);
+        let max_x = used().map(|(p, _)| p.x).max().unwrap_or(0);
+        let max_y = used().map(|(p, _)| p.y).max().unwrap_or(0);
+
+        for y in 0..=max_y {
+            for x in min_x..=max_x {
+                let point = Point2::new(x + self.offset_x, y);
+                let c = match self.get(point) {
+                    Tile::Rock => '#',
+                    Tile::Sand => 'o',
+                    Tile::Air if point == SOURCE => '+',
+                    Tile::Air => '.',
+                };
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    None
+    Some(Cave::parse(input).pour(false))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    None
+    Some(Cave::parse(input).pour(true))
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 14);
+    if pico_args::Arguments::from_env().contains("--cave") {
+        for with_floor in [false, true] {
+            let mut cave = Cave::parse(input);
+            cave.pour(with_floor);
+            println!("{cave}");
+        }
+    }
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -19,12 +167,20 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 14);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input), Some(24));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 14);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(93));
+    }
+
+    #[test]
+    fn test_rock_out_of_reach() {
+        let input = advent_of_code::read_file("examples", 14);
+        let input = format!("300,10 -> 420,10\n{input}");
+        assert_eq!(part_one(&input), Some(24));
+        assert_eq!(part_two(&input), Some(111));
     }
 }
@@ -1,13 +1,138 @@
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+use advent_of_code::helpers::Point2;
+use itertools::Itertools;
+use std::ops::RangeInclusive;
+
+// The real input asks about other coordinates than the example
+const TARGET_ROW: i32 = 2_000_000;
+const SEARCH_BOUND: i32 = 4_000_000;
+
+struct Sensor {
+    position: Point2,
+    beacon: Point2,
+    radius: i32, // distance to the closest beacon
+}
+
+impl Sensor {
+    fn covers(&self, point: Point2) -> bool {
+        self.position.manhattan(point) <= self.radius
+    }
+
+    // The part of a row that is within range, if any
+    fn coverage(&self, y: i32) -> Option<RangeInclusive<i32>> {
+        let reach = self.radius - (self.position.y - y).abs();
+        (reach >= 0).then(|| self.position.x - reach..=self.position.x + reach)
+    }
+}
+
+fn sensors(input: &str) -> Vec<Sensor> {
+    input
+        .lines()
+        .map(|line| {
+            let (sx, sy, bx, by) = line
+                .split(|c: char| !c.is_ascii_digit() && c != '-')
+                .filter(|s| !s.is_empty())
+                .map(|n| n.parse().unwrap())
+                .collect_tuple()
+                .expect("Each line must contain a sensor and a beacon position");
+            let position = Point2::new(sx, sy);
+            let beacon = Point2::new(bx, by);
+            Sensor {
+                position,
+                beacon,
+                radius: position.manhattan(beacon),
+            }
+        })
+        .collect()
+}
+
+// Sorted, non-overlapping ranges of a row covered by any sensor
+fn row_coverage(sensors: &[Sensor], y: i32) -> Vec<RangeInclusive<i32>> {
+    let mut ranges: Vec<_> = sensors.iter().filter_map(|s| s.coverage(y)).collect();
+    ranges.sort_by_key(|r| *r.start());
+
+    let mut merged: Vec<RangeInclusive<i32>> = vec![];
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if *range.start() <= *last.end() + 1 => {
+                *last = *last.start()..=*last.end().max(range.end());
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+// A single uncovered point must lie just outside the range of several sensors,
+// i.e. where the diagonal edges of their diamonds (one further out) cross
+fn edge_intersections(sensors: &[Sensor]) -> impl Iterator<Item = Point2> + '_ {
+    // every edge lies on a line x + y = a or x - y = b
+    let ascending = sensors.iter().flat_map(|s| {
+        let a = s.position.x + s.position.y;
+        [a - s.radius - 1, a + s.radius + 1]
+    });
+    let descending: Vec<i32> = sensors
+        .iter()
+        .flat_map(|s| {
+            let b = s.position.x - s.position.y;
+            [b - s.radius - 1, b + s.radius + 1]
+        })
+        .collect();
+
+    ascending
+        .cartesian_product(descending)
+        .filter(|(a, b)| (a - b) % 2 == 0)
+        .map(|(a, b)| Point2::new((a + b) / 2, (a - b) / 2))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+fn tuning_frequency(point: Point2) -> u64 {
+    point.x as u64 * 4_000_000 + point.y as u64
+}
+
+pub fn part_one(input: &str, row: i32) -> Option<u64> {
+    let sensors = sensors(input);
+    let coverage = row_coverage(&sensors, row);
+    let covered: u64 = coverage
+        .iter()
+        .map(|r| (r.end() - r.start() + 1) as u64)
+        .sum();
+    let beacons = sensors
+        .iter()
+        .map(|s| s.beacon)
+        .filter(|b| b.y == row && coverage.iter().any(|r| r.contains(&b.x)))
+        .unique()
+        .count();
+    Some(covered - beacons as u64)
+}
+
+pub fn part_two(input: &str, bound: i32) -> Option<u64> {
+    let sensors = sensors(input);
+    let in_bounds = |p: &Point2| (0..=bound).contains(&p.x) && (0..=bound).contains(&p.y);
+
+    let beacon = edge_intersections(&sensors)
+        .filter(in_bounds)
+        .find(|&p| !sensors.iter().any(|s| s.covers(p)))
+        // the beacon could still hide in a corner of the search area, where it does
+        // not need sensors on every side. Fall back to looking for a gap in every row.
+        .or_else(|| {
+            (0..=bound).find_map(|y| {
+                let mut x = 0;
+                for range in row_coverage(&sensors, y) {
+                    if *range.start() > x {
+                        break;
+                    }
+                    x = x.max(range.end() + 1);
+                }
+                (x <= bound).then_some(Point2::new(x, y))
+            })
+        })?;
+
+    Some(tuning_frequency(beacon))
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 15);
+    let part_one = |input: &str| part_one(input, TARGET_ROW);
+    let part_two = |input: &str| part_two(input, SEARCH_BOUND);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -19,12 +144,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 15);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input, 10), Some(26));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 15);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input, 20), Some(56000011));
     }
 }
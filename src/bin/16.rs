@@ -1,9 +1,174 @@
+use std::collections::HashMap;
+
+const START: &str = "AA";
+
+// The valves worth opening, with the travel times between all of them. Valve
+// `i` corresponds to bit `i` of a set of opened valves.
+struct Network {
+    flows: Vec<u32>,
+    distances: Vec<Vec<u32>>,
+    start: usize,
+}
+
+impl Network {
+    fn parse(input: &str) -> Self {
+        let valves: Vec<(&str, u32, Vec<&str>)> = input
+            .lines()
+            .map(|line| {
+                let words: Vec<&str> = line.split_whitespace().collect();
+                let flow = words[4]
+                    .trim_start_matches("rate=")
+                    .trim_end_matches(';')
+                    .parse()
+                    .expect("Flow rate must be a u32");
+                let tunnels = words[9..].iter().map(|w| w.trim_end_matches(',')).collect();
+                (words[1], flow, tunnels)
+            })
+            .collect();
+        let index: HashMap<&str, usize> = valves
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _, _))| (*name, i))
+            .collect();
+
+        // Floyd-Warshall over all valves
+        let n = valves.len();
+        let mut distances = vec![vec![u32::MAX / 2; n]; n];
+        for (i, (_, _, tunnels)) in valves.iter().enumerate() {
+            distances[i][i] = 0;
+            for tunnel in tunnels {
+                distances[i][index[tunnel]] = 1;
+            }
+        }
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    let via = distances[i][k] + distances[k][j];
+                    if via < distances[i][j] {
+                        distances[i][j] = via;
+                    }
+                }
+            }
+        }
+
+        // Only keep valves with a flow, plus the start if it has none
+        let start = index[START];
+        let mut kept: Vec<usize> = (0..n).filter(|&i| valves[i].1 > 0).collect();
+        assert!(kept.len() < 32, "Too many valves for a u32 bitmask");
+        let compressed_start = kept.iter().position(|&i| i == start).unwrap_or_else(|| {
+            kept.push(start);
+            kept.len() - 1
+        });
+
+        Self {
+            flows: kept.iter().map(|&i| valves[i].1).collect(),
+            distances: kept
+                .iter()
+                .map(|&i| kept.iter().map(|&j| distances[i][j]).collect())
+                .collect(),
+            start: compressed_start,
+        }
+    }
+
+    fn all_valves(&self) -> u32 {
+        (0..self.flows.len())
+            .filter(|&i| self.flows[i] > 0)
+            .fold(0, |mask, i| mask | 1 << i)
+    }
+
+    // Calls `visit` for every order of opening valves that fits in the time,
+    // with the valves opened so far and the total pressure they will release.
+    // Branches are skipped once `bound` says they cannot improve anything.
+    fn search(
+        &self,
+        position: usize,
+        time_left: u32,
+        opened: u32,
+        released: u32,
+        visit: &mut impl FnMut(u32, u32),
+        bound: &impl Fn(u32) -> bool,
+    ) {
+        visit(opened, released);
+
+        for next in 0..self.flows.len() {
+            if opened & 1 << next != 0 || self.flows[next] == 0 {
+                continue;
+            }
+            let cost = self.distances[position][next] + 1; // travel, then open
+            if cost >= time_left {
+                continue;
+            }
+            let time_left = time_left - cost;
+            let released = released + self.flows[next] * time_left;
+            let opened = opened | 1 << next;
+            if bound(self.optimistic(next, time_left, opened, released)) {
+                self.search(next, time_left, opened, released, visit, bound);
+            }
+        }
+    }
+
+    // An upper bound for what can still be released: as if every closed valve
+    // could be opened as early as if it was the only one left
+    fn optimistic(&self, position: usize, time_left: u32, opened: u32, released: u32) -> u32 {
+        released
+            + (0..self.flows.len())
+                .filter(|&i| opened & 1 << i == 0)
+                .map(|i| self.flows[i] * time_left.saturating_sub(self.distances[position][i] + 1))
+                .sum::<u32>()
+    }
+
+    fn max_pressure(&self, time: u32) -> u32 {
+        let best = std::cell::Cell::new(0);
+        self.search(
+            self.start,
+            time,
+            0,
+            0,
+            &mut |_, released| best.set(best.get().max(released)),
+            &|optimistic| optimistic > best.get(),
+        );
+        best.get()
+    }
+
+    // The most pressure released by opening at most the valves in a set, for every set
+    fn max_pressure_per_subset(&self, time: u32) -> Vec<u32> {
+        let mut best = vec![0; 1 << self.flows.len()];
+        self.search(
+            self.start,
+            time,
+            0,
+            0,
+            &mut |opened, released| {
+                best[opened as usize] = best[opened as usize].max(released);
+            },
+            &|_| true,
+        );
+
+        for bit in 0..self.flows.len() {
+            for mask in 0..best.len() {
+                if mask & 1 << bit != 0 {
+                    best[mask] = best[mask].max(best[mask ^ 1 << bit]);
+                }
+            }
+        }
+        best
+    }
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    None
+    Some(Network::parse(input).max_pressure(30))
 }
 
+// You and the elephant open disjoint sets of valves, so the best team effort is
+// the best combination of one set for you and the rest for the elephant
 pub fn part_two(input: &str) -> Option<u32> {
-    None
+    let network = Network::parse(input);
+    let best = network.max_pressure_per_subset(26);
+    let all = network.all_valves();
+    (0..=all)
+        .filter(|mine| mine & !all == 0)
+        .map(|mine| best[mine as usize] + best[(all ^ mine) as usize])
+        .max()
 }
 
 fn main() {
@@ -19,12 +184,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 16);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input), Some(1651));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 16);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(1707));
     }
 }
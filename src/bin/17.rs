@@ -1,9 +1,138 @@
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+use advent_of_code::helpers::detect_cycle;
+
+const PROFILE_DEPTH: usize = 32;
+
+// Rows of each rock from the bottom up, already two units away from the left
+// wall. Bit 6 is the leftmost column of the chamber, bit 0 the rightmost.
+const ROCKS: [&[u8]; 5] = [
+    &[0b0011110],
+    &[0b0001000, 0b0011100, 0b0001000],
+    &[0b0011100, 0b0000100, 0b0000100],
+    &[0b0010000, 0b0010000, 0b0010000, 0b0010000],
+    &[0b0011000, 0b0011000],
+];
+
+#[derive(Clone, Copy)]
+enum Jet {
+    Left,
+    Right,
+}
+
+struct Chamber {
+    rows: Vec<u8>, // settled rock, bottom row first, no empty rows on top
+    jets: Vec<Jet>,
+    next_jet: usize,
+    next_rock: usize,
+}
+
+// Everything that decides how the tower grows from here on, plus its height
+#[derive(Clone, Copy)]
+struct State {
+    rock: usize,
+    jet: usize,
+    profile: [u8; PROFILE_DEPTH], // the top rows of the tower
+    height: usize,
+}
+
+impl Chamber {
+    fn new(input: &str) -> Self {
+        let jets = input
+            .trim()
+            .chars()
+            .map(|c| match c {
+                '<' => Jet::Left,
+                '>' => Jet::Right,
+                _ => panic!("Expected < or >, got {c:?}"),
+            })
+            .collect();
+
+        Self {
+            rows: vec![],
+            jets,
+            next_jet: 0,
+            next_rock: 0,
+        }
+    }
+
+    fn collides(&self, rock: &[u8], bottom: usize) -> bool {
+        rock.iter()
+            .enumerate()
+            .any(|(i, row)| self.rows.get(bottom + i).is_some_and(|r| r & row != 0))
+    }
+
+    fn drop_rock(&mut self) {
+        let mut rock = ROCKS[self.next_rock].to_vec();
+        self.next_rock = (self.next_rock + 1) % ROCKS.len();
+        let mut bottom = self.rows.len() + 3;
+
+        loop {
+            let jet = self.jets[self.next_jet];
+            self.next_jet = (self.next_jet + 1) % self.jets.len();
+            let pushed: Option<Vec<u8>> = match jet {
+                Jet::Left => rock
+                    .iter()
+                    .map(|row| (row & 0b1000000 == 0).then_some(row << 1))
+                    .collect(),
+                Jet::Right => rock
+                    .iter()
+                    .map(|row| (row & 0b0000001 == 0).then_some(row >> 1))
+                    .collect(),
+            };
+            if let Some(pushed) = pushed {
+                if !self.collides(&pushed, bottom) {
+                    rock = pushed;
+                }
+            }
+
+            if bottom == 0 || self.collides(&rock, bottom - 1) {
+                break;
+            }
+            bottom -= 1;
+        }
+
+        for (i, row) in rock.into_iter().enumerate() {
+            match self.rows.get_mut(bottom + i) {
+                Some(existing) => *existing |= row,
+                None => self.rows.push(row),
+            }
+        }
+    }
+
+    fn state(&self) -> State {
+        let mut profile = [0; PROFILE_DEPTH];
+        for (slot, row) in profile.iter_mut().zip(self.rows.iter().rev()) {
+            *slot = *row;
+        }
+        State {
+            rock: self.next_rock,
+            jet: self.next_jet,
+            profile,
+            height: self.rows.len(),
+        }
+    }
+}
+
+// Drops rocks until the chamber gets back into a state it was in before, then
+// skips over as many repetitions of that cycle as fit into the remaining rocks
+fn tower_height(input: &str, rocks: usize) -> u64 {
+    let mut chamber = Chamber::new(input);
+    let (cycle, history) = detect_cycle(
+        chamber.state(),
+        |_| {
+            chamber.drop_rock();
+            chamber.state()
+        },
+        |state| (state.rock, state.jet, state.profile),
+    );
+    cycle.extrapolate(rocks, |step| history[step].height as i64) as u64
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    Some(tower_height(input, 2022))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+pub fn part_two(input: &str) -> Option<u64> {
+    Some(tower_height(input, 1_000_000_000_000))
 }
 
 fn main() {
@@ -19,12 +148,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 17);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input), Some(3068));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 17);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(1514285714288));
     }
 }
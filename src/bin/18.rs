@@ -1,9 +1,119 @@
+use advent_of_code::helpers::Point3;
+use itertools::Itertools;
+
+// A dense set of points within a box, one bit per point. The box leaves a
+// layer of empty space around the droplet, so the outside is connected.
+struct VoxelGrid {
+    min: Point3,
+    size: Point3,
+    bits: Vec<u64>,
+}
+
+impl VoxelGrid {
+    fn new(min: Point3, max: Point3) -> Self {
+        let size = max - min + Point3::new(1, 1, 1);
+        let len = (size.x * size.y * size.z) as usize;
+        Self {
+            min,
+            size,
+            bits: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    fn index(&self, point: Point3) -> Option<usize> {
+        let p = point - self.min;
+        let inside = (0..self.size.x).contains(&p.x)
+            && (0..self.size.y).contains(&p.y)
+            && (0..self.size.z).contains(&p.z);
+        inside.then(|| ((p.x * self.size.y + p.y) * self.size.z + p.z) as usize)
+    }
+
+    // Whether a point is part of the box at all
+    fn in_bounds(&self, point: Point3) -> bool {
+        self.index(point).is_some()
+    }
+
+    fn contains(&self, point: Point3) -> bool {
+        self.index(point)
+            .is_some_and(|i| self.bits[i / 64] & 1 << (i % 64) != 0)
+    }
+
+    fn insert(&mut self, point: Point3) -> bool {
+        let i = self.index(point).expect("Point must be within the grid");
+        let absent = self.bits[i / 64] & 1 << (i % 64) == 0;
+        self.bits[i / 64] |= 1 << (i % 64);
+        absent
+    }
+}
+
+fn cubes(input: &str) -> Vec<Point3> {
+    input
+        .lines()
+        .map(|line| {
+            let (x, y, z) = line
+                .split(',')
+                .map(|n| n.parse().expect("Coordinates must be i32"))
+                .collect_tuple()
+                .expect("Each cube must have three coordinates");
+            Point3::new(x, y, z)
+        })
+        .collect()
+}
+
+fn droplet(cubes: &[Point3]) -> VoxelGrid {
+    let padding = Point3::new(1, 1, 1);
+    let min = Point3::new(
+        cubes.iter().map(|c| c.x).min().unwrap_or(0),
+        cubes.iter().map(|c| c.y).min().unwrap_or(0),
+        cubes.iter().map(|c| c.z).min().unwrap_or(0),
+    );
+    let max = Point3::new(
+        cubes.iter().map(|c| c.x).max().unwrap_or(0),
+        cubes.iter().map(|c| c.y).max().unwrap_or(0),
+        cubes.iter().map(|c| c.z).max().unwrap_or(0),
+    );
+
+    let mut grid = VoxelGrid::new(min - padding, max + padding);
+    for &cube in cubes {
+        grid.insert(cube);
+    }
+    grid
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    None
+    let cubes = cubes(input);
+    let droplet = droplet(&cubes);
+    let exposed = cubes
+        .iter()
+        .flat_map(|cube| cube.neighbours())
+        .filter(|&n| !droplet.contains(n))
+        .count();
+    Some(exposed as u32)
 }
 
+// Flood fills the air around the droplet, starting from a corner of the
+// padded box, and counts every time it bumps into a face of the droplet
 pub fn part_two(input: &str) -> Option<u32> {
-    None
+    let droplet = droplet(&cubes(input));
+    let mut outside = VoxelGrid::new(
+        droplet.min,
+        droplet.min + droplet.size - Point3::new(1, 1, 1),
+    );
+    let mut stack = vec![droplet.min];
+    outside.insert(droplet.min);
+
+    let mut exterior_faces = 0;
+    while let Some(air) = stack.pop() {
+        for neighbour in air.neighbours().filter(|&n| droplet.in_bounds(n)) {
+            if droplet.contains(neighbour) {
+                exterior_faces += 1;
+            } else if outside.insert(neighbour) {
+                stack.push(neighbour);
+            }
+        }
+    }
+
+    Some(exterior_faces)
 }
 
 fn main() {
@@ -19,12 +129,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 18);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input), Some(64));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 18);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(58));
     }
 }
@@ -1,9 +1,142 @@
+use itertools::Itertools;
+use std::thread;
+
+const ORE: usize = 0;
+const CLAY: usize = 1;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+const RESOURCES: [usize; 3] = [ORE, CLAY, OBSIDIAN];
+
+struct Blueprint {
+    id: u32,
+    costs: [[u32; 3]; 4], // ore, clay and obsidian needed for each kind of robot
+    max_spend: [u32; 3],  // the most of each resource any robot needs
+}
+
+impl Blueprint {
+    fn parse(line: &str) -> Self {
+        let (id, ore_ore, clay_ore, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian) = line
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|s| !s.is_empty())
+            .map(|n| n.parse().unwrap())
+            .collect_tuple()
+            .expect("Blueprint must have an id and six costs");
+
+        let costs = [
+            [ore_ore, 0, 0],
+            [clay_ore, 0, 0],
+            [obsidian_ore, obsidian_clay, 0],
+            [geode_ore, 0, geode_obsidian],
+        ];
+        let max_spend =
+            RESOURCES.map(|resource| costs.iter().map(|cost| cost[resource]).max().unwrap());
+
+        Self {
+            id,
+            costs,
+            max_spend,
+        }
+    }
+
+    fn max_geodes(&self, minutes: u32) -> u32 {
+        let mut best = 0;
+        self.search(minutes, [1, 0, 0, 0], [0, 0, 0], 0, &mut best);
+        best
+    }
+
+    // Rather than simulating every minute, decides which robot to build next
+    // and skips ahead to the minute it gets built. Geodes are counted as soon as
+    // a geode robot is built, for all the minutes it is going to crack them.
+    fn search(
+        &self,
+        time_left: u32,
+        robots: [u32; 4],
+        resources: [u32; 3],
+        geodes: u32,
+        best: &mut u32,
+    ) {
+        *best = (*best).max(geodes);
+
+        // even building a geode robot every remaining minute cannot beat the best
+        if geodes + time_left * time_left.saturating_sub(1) / 2 <= *best {
+            return;
+        }
+
+        for robot in (0..4).rev() {
+            // there is no point in producing more of a resource than can be spent in a minute
+            if robot != GEODE && robots[robot] >= self.max_spend[robot] {
+                continue;
+            }
+            let Some(wait) = self.time_until_affordable(robot, robots, resources) else {
+                continue;
+            };
+            if wait + 1 >= time_left {
+                continue;
+            }
+
+            let elapsed = wait + 1;
+            let time_left = time_left - elapsed;
+            let resources =
+                RESOURCES.map(|r| resources[r] + robots[r] * elapsed - self.costs[robot][r]);
+            if robot == GEODE {
+                self.search(time_left, robots, resources, geodes + time_left, best);
+            } else {
+                let mut robots = robots;
+                robots[robot] += 1;
+                self.search(time_left, robots, resources, geodes, best);
+            }
+        }
+    }
+
+    // Minutes of collecting until a robot can be built, `None` if it never can
+    fn time_until_affordable(
+        &self,
+        robot: usize,
+        robots: [u32; 4],
+        resources: [u32; 3],
+    ) -> Option<u32> {
+        RESOURCES
+            .into_iter()
+            .map(|r| {
+                let missing = self.costs[robot][r].saturating_sub(resources[r]);
+                match (missing, robots[r]) {
+                    (0, _) => Some(0),
+                    (_, 0) => None,
+                    (missing, rate) => Some(missing.div_ceil(rate)),
+                }
+            })
+            .try_fold(0, |wait, w| w.map(|w| wait.max(w)))
+    }
+}
+
+// Evaluates every blueprint on its own thread
+fn max_geodes(blueprints: &[Blueprint], minutes: u32) -> Vec<u32> {
+    thread::scope(|scope| {
+        let handles: Vec<_> = blueprints
+            .iter()
+            .map(|blueprint| scope.spawn(move || blueprint.max_geodes(minutes)))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    })
+}
+
+fn blueprints(input: &str) -> Vec<Blueprint> {
+    input.lines().map(Blueprint::parse).collect()
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    None
+    let blueprints = blueprints(input);
+    let quality_levels = max_geodes(&blueprints, 24)
+        .into_iter()
+        .zip(blueprints.iter())
+        .map(|(geodes, blueprint)| geodes * blueprint.id);
+    Some(quality_levels.sum())
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    None
+    let blueprints = blueprints(input);
+    let first = &blueprints[..blueprints.len().min(3)];
+    Some(max_geodes(first, 32).into_iter().product())
 }
 
 fn main() {
@@ -19,12 +152,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 19);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input), Some(33));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 19);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(56 * 62));
     }
 }
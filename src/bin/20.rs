@@ -1,9 +1,143 @@
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+const DECRYPTION_KEY: i64 = 811_589_153;
+const GROVE_OFFSETS: [usize; 3] = [1000, 2000, 3000];
+
+// The numbers, by their index in the original file, split into buckets of
+// about sqrt(len) each. Finding, removing and inserting a number only walks
+// the bucket lengths and a single bucket, so a move is O(sqrt(len)) instead
+// of shifting the whole list around.
+struct MixingList {
+    values: Vec<i64>,
+    buckets: Vec<Vec<usize>>,
+    bucket_of: Vec<usize>,
+    bucket_size: usize,
+}
+
+impl MixingList {
+    fn new(values: Vec<i64>) -> Self {
+        let bucket_size = (values.len() as f64).sqrt().ceil().max(1.0) as usize;
+        let mut list = Self {
+            buckets: vec![(0..values.len()).collect()],
+            bucket_of: vec![0; values.len()],
+            values,
+            bucket_size,
+        };
+        list.rebalance();
+        list
+    }
+
+    // Splits the current order back into evenly sized buckets
+    fn rebalance(&mut self) {
+        let order: Vec<usize> = self.buckets.concat();
+        self.buckets = order
+            .chunks(self.bucket_size)
+            .map(|chunk| chunk.to_vec())
+            .collect();
+        for (b, bucket) in self.buckets.iter().enumerate() {
+            for &i in bucket {
+                self.bucket_of[i] = b;
+            }
+        }
+    }
+
+    fn position(&self, i: usize) -> usize {
+        let b = self.bucket_of[i];
+        let before: usize = self.buckets[..b].iter().map(Vec::len).sum();
+        let offset = self.buckets[b].iter().position(|&j| j == i).unwrap();
+        before + offset
+    }
+
+    fn remove(&mut self, i: usize) {
+        let bucket = &mut self.buckets[self.bucket_of[i]];
+        let offset = bucket.iter().position(|&j| j == i).unwrap();
+        bucket.remove(offset);
+    }
+
+    fn insert(&mut self, i: usize, mut position: usize) {
+        let last = self.buckets.len() - 1;
+        for (b, bucket) in self.buckets.iter_mut().enumerate() {
+            if position <= bucket.len() && (position < bucket.len() || b == last) {
+                bucket.insert(position, i);
+                self.bucket_of[i] = b;
+                return;
+            }
+            position -= bucket.len();
+        }
+    }
+
+    // Moves every number, in original order, as many places as its value.
+    // While a number moves it is not part of the circle, so the circle it
+    // moves around only has `len - 1` places and moves wrap modulo that.
+    fn mix(&mut self) {
+        let len = self.values.len();
+        if len < 2 {
+            return;
+        }
+        let places = len as i64 - 1;
+
+        for i in 0..len {
+            if self.values[i].rem_euclid(places) == 0 {
+                continue;
+            }
+            let from = self.position(i) as i64;
+            self.remove(i);
+            let to = (from + self.values[i]).rem_euclid(places) as usize;
+            self.insert(i, to);
+
+            // keep any one bucket from growing much past sqrt(len)
+            if (i + 1) % self.bucket_size == 0 {
+                self.rebalance();
+            }
+        }
+    }
+
+    // The numbers in their current order, starting at 0
+    fn order_from_zero(&self) -> Vec<i64> {
+        let mut order: Vec<i64> = self
+            .buckets
+            .concat()
+            .into_iter()
+            .map(|i| self.values[i])
+            .collect();
+        let zero = order
+            .iter()
+            .position(|&v| v == 0)
+            .expect("The file must contain a 0");
+        order.rotate_left(zero);
+        order
+    }
+
+    fn grove_coordinates(&self) -> i64 {
+        let order = self.order_from_zero();
+        GROVE_OFFSETS
+            .iter()
+            .map(|offset| order[offset % order.len()])
+            .sum()
+    }
+}
+
+fn numbers(input: &str) -> Vec<i64> {
+    input
+        .lines()
+        .map(|line| line.parse().expect("Every line must be an i64"))
+        .collect()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+pub fn part_one(input: &str) -> Option<i64> {
+    let mut list = MixingList::new(numbers(input));
+    list.mix();
+    Some(list.grove_coordinates())
+}
+
+pub fn part_two(input: &str) -> Option<i64> {
+    let values = numbers(input)
+        .into_iter()
+        .map(|v| v * DECRYPTION_KEY)
+        .collect();
+    let mut list = MixingList::new(values);
+    for _ in 0..10 {
+        list.mix();
+    }
+    Some(list.grove_coordinates())
 }
 
 fn main() {
@@ -19,12 +153,55 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 20);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input), Some(3));
+    }
+
+    #[test]
+    fn test_mix() {
+        let mut list = MixingList::new(vec![1, 2, -3, 3, -2, 0, 4]);
+        list.mix();
+        assert_eq!(list.order_from_zero(), vec![0, 3, -2, 1, 2, -3, 4]);
+    }
+
+    // Mixing by shifting a plain Vec around, to check the buckets against
+    fn naive_mix(values: &[i64], rounds: usize) -> Vec<i64> {
+        let places = values.len() as i64 - 1;
+        let mut order: Vec<usize> = (0..values.len()).collect();
+        for _ in 0..rounds {
+            for (i, value) in values.iter().enumerate() {
+                let from = order.iter().position(|&j| j == i).unwrap();
+                order.remove(from);
+                let to = (from as i64 + value).rem_euclid(places) as usize;
+                order.insert(to, i);
+            }
+        }
+        let zero = order.iter().position(|&i| values[i] == 0).unwrap();
+        order.rotate_left(zero);
+        order.into_iter().map(|i| values[i]).collect()
+    }
+
+    #[test]
+    fn test_mix_matches_naive() {
+        // a simple LCG, so the test needs no extra dependencies
+        let mut seed: i64 = 20;
+        let mut values: Vec<i64> = (0..2000)
+            .map(|_| {
+                seed = (seed * 1_103_515_245 + 12_345) % (1 << 31);
+                (seed % 20_001 - 10_000) * DECRYPTION_KEY
+            })
+            .collect();
+        values[1234] = 0;
+
+        let mut list = MixingList::new(values.clone());
+        for _ in 0..3 {
+            list.mix();
+        }
+        assert_eq!(list.order_from_zero(), naive_mix(&values, 3));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 20);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(1623178306));
     }
 }
@@ -1,15 +1,164 @@
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+use std::collections::HashMap;
+
+const ROOT: &str = "root";
+const HUMAN: &str = "humn";
+
+#[derive(Clone, Copy)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Operator {
+    fn apply(self, left: i64, right: i64) -> i64 {
+        match self {
+            Operator::Add => left + right,
+            Operator::Subtract => left - right,
+            Operator::Multiply => left * right,
+            Operator::Divide => left / right,
+        }
+    }
+}
+
+impl TryFrom<&str> for Operator {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "+" => Ok(Operator::Add),
+            "-" => Ok(Operator::Subtract),
+            "*" => Ok(Operator::Multiply),
+            "/" => Ok(Operator::Divide),
+            _ => Err(format!("Expected one of + - * /, got {s:?}")),
+        }
+    }
+}
+
+enum Job<'a> {
+    Number(i64),
+    Operation(&'a str, Operator, &'a str),
+}
+
+enum Expr {
+    Number(i64),
+    Human, // the unknown value in part two
+    Binary(Box<Expr>, Operator, Box<Expr>),
+}
+
+impl Expr {
+    // Builds the tree of the monkey `name`. With `human_unknown`, the value
+    // of the human is left open instead of taking the number from the input.
+    fn build(jobs: &HashMap<&str, Job>, name: &str, human_unknown: bool) -> Self {
+        if human_unknown && name == HUMAN {
+            return Expr::Human;
+        }
+        match jobs[name] {
+            Job::Number(n) => Expr::Number(n),
+            Job::Operation(left, op, right) => Expr::Binary(
+                Box::new(Self::build(jobs, left, human_unknown)),
+                op,
+                Box::new(Self::build(jobs, right, human_unknown)),
+            ),
+        }
+    }
+
+    // `None` if the value depends on the human
+    fn evaluate(&self) -> Option<i64> {
+        match self {
+            Expr::Number(n) => Some(*n),
+            Expr::Human => None,
+            Expr::Binary(left, op, right) => Some(op.apply(left.evaluate()?, right.evaluate()?)),
+        }
+    }
+
+    // Finds the value of the human that makes this expression equal `target`, by
+    // undoing one operation at a time on the way down to the human
+    fn solve(&self, target: i64) -> Result<i64, String> {
+        let (left, op, right) = match self {
+            Expr::Human => return Ok(target),
+            Expr::Number(_) => return Err(format!("{HUMAN} is not part of the expression")),
+            Expr::Binary(left, op, right) => (left, *op, right),
+        };
+
+        let exact = |dividend: i64, divisor: i64| match divisor {
+            0 => Err("Division by zero".to_string()),
+            _ if dividend % divisor != 0 => {
+                Err(format!("{dividend} is not divisible by {divisor}"))
+            }
+            _ => Ok(dividend / divisor),
+        };
+
+        match (left.evaluate(), right.evaluate()) {
+            // target = unknown <op> right
+            (None, Some(right)) => left.solve(match op {
+                Operator::Add => target - right,
+                Operator::Subtract => target + right,
+                Operator::Multiply => exact(target, right)?,
+                Operator::Divide => target * right,
+            }),
+            // target = left <op> unknown
+            (Some(left), None) => right.solve(match op {
+                Operator::Add => target - left,
+                Operator::Subtract => left - target,
+                Operator::Multiply => exact(target, left)?,
+                Operator::Divide => exact(left, target)?,
+            }),
+            (None, None) => Err(format!("{HUMAN} appears on both sides of an operation")),
+            (Some(_), Some(_)) => Err(format!("{HUMAN} is not part of the expression")),
+        }
+    }
+}
+
+fn jobs(input: &str) -> HashMap<&str, Job<'_>> {
+    input
+        .lines()
+        .map(|line| {
+            let (name, job) = line.split_once(": ").expect("Expected <name>: <job>");
+            let job = match job.split(' ').collect::<Vec<_>>()[..] {
+                [left, op, right] => Job::Operation(left, Operator::try_from(op).unwrap(), right),
+                [number] => Job::Number(number.parse().expect("Expected an i64")),
+                _ => panic!("Unexpected job {job:?}"),
+            };
+            (name, job)
+        })
+        .collect()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+// The root monkey compares both of its operands for equality, so whichever
+// side does not depend on the human is the value the other side has to match
+fn human_value(input: &str) -> Result<i64, String> {
+    let jobs = jobs(input);
+    let Job::Operation(left, _, right) = jobs[ROOT] else {
+        return Err(format!("{ROOT} must compare two monkeys"));
+    };
+    let left = Expr::build(&jobs, left, true);
+    let right = Expr::build(&jobs, right, true);
+
+    match (left.evaluate(), right.evaluate()) {
+        (None, Some(target)) => left.solve(target),
+        (Some(target), None) => right.solve(target),
+        (None, None) => Err(format!("{HUMAN} appears on both sides of {ROOT}")),
+        (Some(_), Some(_)) => Err(format!("{HUMAN} is not part of the expression")),
+    }
+}
+
+pub fn part_one(input: &str) -> Option<i64> {
+    Expr::build(&jobs(input), ROOT, false).evaluate()
+}
+
+pub fn part_two(input: &str) -> Option<i64> {
+    human_value(input).ok()
 }
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 21);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
+    if let Err(e) = human_value(input) {
+        eprintln!("{e}");
+    }
 }
 
 #[cfg(test)]
@@ -19,12 +168,15 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 21);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input), Some(152));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 21);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(301));
+
+        let input = "root: aaaa + bbbb\naaaa: humn * cccc\nbbbb: humn + cccc\ncccc: 2\nhumn: 1";
+        assert!(human_value(input).is_err());
     }
 }
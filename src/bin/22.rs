@@ -1,9 +1,264 @@
+use advent_of_code::helpers::{Dir4, Grid, Point2, Point3};
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Void,
+    Open,
+    Wall,
+}
+
+enum Step {
+    Forward(u32),
+    TurnLeft,
+    TurnRight,
+}
+
+struct Board {
+    tiles: Grid<Tile>,
+}
+
+impl Board {
+    fn parse(map: &str) -> Self {
+        let width = map.lines().map(|line| line.len()).max().unwrap_or(0);
+        let height = map.lines().count();
+        let mut tiles = Grid::new(width, height, Tile::Void);
+        for (y, line) in map.lines().enumerate() {
+            for (x, b) in line.bytes().enumerate() {
+                tiles[Point2::from((x, y))] = match b {
+                    b'.' => Tile::Open,
+                    b'#' => Tile::Wall,
+                    _ => Tile::Void,
+                };
+            }
+        }
+        Self { tiles }
+    }
+
+    fn tile(&self, point: Point2) -> Tile {
+        self.tiles.get(point).copied().unwrap_or(Tile::Void)
+    }
+
+    // Follows the path from the leftmost open tile of the top row, calling
+    // `wrap` whenever a step would leave the board. Returns the final password.
+    fn walk(&self, path: &[Step], wrap: impl Fn(Point2, Dir4) -> (Point2, Dir4)) -> u32 {
+        let mut position = self
+            .tiles
+            .position(|&tile| tile == Tile::Open)
+            .expect("The board must have an open tile");
+        let mut facing = Dir4::Right;
+
+        for step in path {
+            match *step {
+                Step::TurnLeft => facing = facing.turn_left(),
+                Step::TurnRight => facing = facing.turn_right(),
+                Step::Forward(n) => {
+                    for _ in 0..n {
+                        let (next, next_facing) = match self.tile(position + facing) {
+                            Tile::Void => wrap(position, facing),
+                            _ => (position + facing, facing),
+                        };
+                        if self.tile(next) == Tile::Wall {
+                            break;
+                        }
+                        position = next;
+                        facing = next_facing;
+                    }
+                }
+            }
+        }
+
+        1000 * (position.y as u32 + 1) + 4 * (position.x as u32 + 1) + facing as u32
+    }
+
+    // Wraps around to the other end of the row or column
+    fn flat_wrap(&self, position: Point2, facing: Dir4) -> (Point2, Dir4) {
+        let back = facing.reverse();
+        let mut wrapped = position;
+        while self.tile(wrapped + back) != Tile::Void {
+            wrapped += back;
+        }
+        (wrapped, facing)
+    }
+}
+
+// How a face of the net lies on the cube: its outward normal, and the directions
+// that moving right and down on the net correspond to
+#[derive(Clone, Copy)]
+struct Orientation {
+    normal: Point3,
+    right: Point3,
+    down: Point3,
+}
+
+impl Orientation {
+    fn towards(&self, dir: Dir4) -> Point3 {
+        match dir {
+            Dir4::Right => self.right,
+            Dir4::Down => self.down,
+            Dir4::Left => -self.right,
+            Dir4::Up => -self.down,
+        }
+    }
+
+    // The orientation of the neighbouring face on the net, folded over the shared edge
+    fn fold(&self, dir: Dir4) -> Self {
+        let Orientation {
+            normal,
+            right,
+            down,
+        } = *self;
+        match dir {
+            Dir4::Right => Orientation {
+                normal: right,
+                right: -normal,
+                down,
+            },
+            Dir4::Down => Orientation {
+                normal: down,
+                right,
+                down: -normal,
+            },
+            Dir4::Left => Orientation {
+                normal: -right,
+                right: normal,
+                down,
+            },
+            Dir4::Up => Orientation {
+                normal: -down,
+                right,
+                down: normal,
+            },
+        }
+    }
+}
+
+// The board folded into a cube. Any net works: the faces are folded one by one
+// starting from the first one, which tells which face lies in every direction.
+struct Cube {
+    size: i32,
+    faces: HashMap<Point2, Orientation>, // keyed by position of the face on the net
+}
+
+impl Cube {
+    fn fold(board: &Board) -> Self {
+        let area = board.tiles.iter().filter(|(_, &t)| t != Tile::Void).count();
+        let size = ((area / 6) as f64).sqrt() as i32;
+        assert_eq!(
+            (size * size * 6) as usize,
+            area,
+            "The board must be a cube net"
+        );
+
+        let is_face = |face: Point2| board.tile(face * size) != Tile::Void;
+        let first = board.tiles.position(|&t| t != Tile::Void).unwrap();
+        let first = Point2::new(first.x / size, first.y / size);
+
+        let mut faces = HashMap::from([(
+            first,
+            Orientation {
+                normal: Point3::new(0, 0, -1),
+                right: Point3::new(1, 0, 0),
+                down: Point3::new(0, 1, 0),
+            },
+        )]);
+        let mut queue = VecDeque::from([first]);
+        while let Some(face) = queue.pop_front() {
+            let orientation = faces[&face];
+            for dir in Dir4::ALL {
+                let neighbour = face + dir;
+                if neighbour.x >= 0
+                    && neighbour.y >= 0
+                    && is_face(neighbour)
+                    && !faces.contains_key(&neighbour)
+                {
+                    faces.insert(neighbour, orientation.fold(dir));
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        assert_eq!(faces.len(), 6, "The board must be a cube net");
+
+        Self { size, faces }
+    }
+
+    // Walks over the edge of a face onto the face that is adjacent on the cube.
+    //
+    // Going clockwise around its face, every edge is walked in the opposite
+    // direction from how the adjacent face walks it, so the distance along the
+    // edge can be mirrored from one face to the other.
+    fn wrap(&self, position: Point2, facing: Dir4) -> (Point2, Dir4) {
+        let n = self.size;
+        let from = Point2::new(position.x / n, position.y / n);
+        let local = position - from * n;
+        let orientation = self.faces[&from];
+
+        let along = match facing {
+            Dir4::Up => local.x,
+            Dir4::Right => local.y,
+            Dir4::Down => n - 1 - local.x,
+            Dir4::Left => n - 1 - local.y,
+        };
+
+        let target = orientation.towards(facing);
+        let (&to, to_orientation) = self
+            .faces
+            .iter()
+            .find(|(_, o)| o.normal == target)
+            .expect("Every direction leads to a face");
+        let edge = Dir4::ALL
+            .into_iter()
+            .find(|&dir| to_orientation.towards(dir) == orientation.normal)
+            .expect("Adjacent faces share an edge");
+
+        let along = n - 1 - along;
+        let local = match edge {
+            Dir4::Up => Point2::new(along, 0),
+            Dir4::Right => Point2::new(n - 1, along),
+            Dir4::Down => Point2::new(n - 1 - along, n - 1),
+            Dir4::Left => Point2::new(0, n - 1 - along),
+        };
+        (to * n + local, edge.reverse())
+    }
+}
+
+fn parse(input: &str) -> (Board, Vec<Step>) {
+    let (map, path) = input
+        .split_once("\n\n")
+        .expect("Map and path are separated by an empty line");
+
+    let mut steps = vec![];
+    let mut distance = None;
+    for c in path.trim().chars() {
+        if let Some(digit) = c.to_digit(10) {
+            distance = Some(distance.unwrap_or(0) * 10 + digit);
+            continue;
+        }
+        if let Some(n) = distance.take() {
+            steps.push(Step::Forward(n));
+        }
+        steps.push(match c {
+            'L' => Step::TurnLeft,
+            'R' => Step::TurnRight,
+            _ => panic!("Unexpected {c:?} in path"),
+        });
+    }
+    if let Some(n) = distance {
+        steps.push(Step::Forward(n));
+    }
+
+    (Board::parse(map), steps)
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    None
+    let (board, path) = parse(input);
+    Some(board.walk(&path, |p, dir| board.flat_wrap(p, dir)))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    None
+    let (board, path) = parse(input);
+    let cube = Cube::fold(&board);
+    Some(board.walk(&path, |p, dir| cube.wrap(p, dir)))
 }
 
 fn main() {
@@ -19,12 +274,47 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 22);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input), Some(6032));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 22);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(5031));
+    }
+
+    // Walking over any edge and turning around must lead back to the start,
+    // both for the example net and for the shape of the real input's net
+    #[test]
+    fn test_cube_edges_are_reversible() {
+        let example = advent_of_code::read_file("examples", 22);
+        let real_shape = " ##\n # \n## \n#  "
+            .lines()
+            .flat_map(|row| {
+                let row: String = row
+                    .chars()
+                    .map(|c| if c == '#' { "...." } else { "    " })
+                    .collect();
+                vec![row; 4]
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        for map in [example.split_once("\n\n").unwrap().0, &real_shape] {
+            let board = Board::parse(map);
+            let cube = Cube::fold(&board);
+            for (position, _) in board.tiles.iter().filter(|(_, &t)| t != Tile::Void) {
+                for facing in Dir4::ALL {
+                    if board.tile(position + facing) != Tile::Void {
+                        continue;
+                    }
+                    let (next, next_facing) = cube.wrap(position, facing);
+                    assert_eq!(
+                        cube.wrap(next, next_facing.reverse()),
+                        (position, facing.reverse())
+                    );
+                }
+            }
+        }
     }
 }
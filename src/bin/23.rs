@@ -1,9 +1,92 @@
+use advent_of_code::helpers::{Dir8, Grid, Point2};
+use std::collections::{HashMap, HashSet};
+
+// The directions an elf considers moving in, with the neighbours that have to
+// be free for it, in the order of the first round
+const PROPOSALS: [(Dir8, [Dir8; 3]); 4] = [
+    (Dir8::N, [Dir8::N, Dir8::NE, Dir8::NW]),
+    (Dir8::S, [Dir8::S, Dir8::SE, Dir8::SW]),
+    (Dir8::W, [Dir8::W, Dir8::NW, Dir8::SW]),
+    (Dir8::E, [Dir8::E, Dir8::NE, Dir8::SE]),
+];
+
+// The elves as a sparse set of positions, so the grove can spread out freely
+struct Grove {
+    elves: HashSet<Point2>,
+    round: usize,
+}
+
+impl Grove {
+    fn parse(input: &str) -> Self {
+        let scan = Grid::parse(input, |b| match b {
+            b'#' => Ok(true),
+            b'.' => Ok(false),
+            _ => Err(format!("Unexpected tile {:?}", b as char)),
+        })
+        .unwrap();
+        let elves = scan
+            .iter()
+            .filter(|(_, &elf)| elf)
+            .map(|(p, _)| p)
+            .collect();
+        Self { elves, round: 0 }
+    }
+
+    fn proposal(&self, elf: Point2) -> Option<Point2> {
+        let free = |dir: Dir8| !self.elves.contains(&(elf + dir));
+        if Dir8::ALL.into_iter().all(free) {
+            return None;
+        }
+        (0..PROPOSALS.len())
+            .map(|i| PROPOSALS[(self.round + i) % PROPOSALS.len()])
+            .find(|(_, checks)| checks.iter().all(|&dir| free(dir)))
+            .map(|(dir, _)| elf + dir)
+    }
+
+    // Plays one round and returns whether any elf moved
+    fn play_round(&mut self) -> bool {
+        // for every tile, an elf that wants to move there and how many elves do
+        let mut proposals: HashMap<Point2, (Point2, u8)> = HashMap::new();
+        for &elf in &self.elves {
+            if let Some(target) = self.proposal(elf) {
+                proposals.entry(target).or_insert((elf, 0)).1 += 1;
+            }
+        }
+
+        let mut moved = false;
+        for (target, (elf, count)) in proposals {
+            if count == 1 {
+                self.elves.remove(&elf);
+                self.elves.insert(target);
+                moved = true;
+            }
+        }
+        self.round += 1;
+        moved
+    }
+
+    fn empty_ground(&self) -> u32 {
+        let min_x = self.elves.iter().map(|e| e.x).min().unwrap_or(0);
+        let max_x = self.elves.iter().map(|e| e.x).max().unwrap_or(0);
+        let min_y = self.elves.iter().map(|e| e.y).min().unwrap_or(0);
+        let max_y = self.elves.iter().map(|e| e.y).max().unwrap_or(0);
+        let area = (max_x - min_x + 1) * (max_y - min_y + 1);
+        area as u32 - self.elves.len() as u32
+    }
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    None
+    let mut grove = Grove::parse(input);
+    for _ in 0..10 {
+        grove.play_round();
+    }
+    Some(grove.empty_ground())
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    None
+    let mut grove = Grove::parse(input);
+    while grove.play_round() {}
+    Some(grove.round as u32)
 }
 
 fn main() {
@@ -19,12 +102,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 23);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input), Some(110));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 23);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(20));
     }
 }
@@ -1,9 +1,112 @@
+use advent_of_code::helpers::{lcm, Dir4, Grid, Point2};
+use std::collections::VecDeque;
+
+// The inside of the valley, without its walls. Blizzards move in straight
+// lines and wrap around, so the whole valley repeats every `period` minutes
+// and whether a tile is free at some time can be looked up directly from
+// where blizzards started out.
+struct Valley {
+    blizzards: Grid<u8>,
+    start: Point2, // just above the top left tile
+    goal: Point2,  // just below the bottom right tile
+    period: usize,
+}
+
+impl Valley {
+    fn parse(input: &str) -> Self {
+        let lines: Vec<&str> = input.lines().collect();
+        let inner: String = lines[1..lines.len() - 1]
+            .iter()
+            .map(|line| &line[1..line.len() - 1])
+            .collect::<Vec<_>>()
+            .join("\n");
+        let blizzards = Grid::parse(&inner, |b| match b {
+            b'.' | b'>' | b'<' | b'^' | b'v' => Ok(b),
+            _ => Err(format!("Unexpected tile {:?}", b as char)),
+        })
+        .unwrap();
+
+        let (width, height) = (blizzards.width, blizzards.height);
+        Self {
+            start: Point2::new(0, -1),
+            goal: Point2::from((width - 1, height)),
+            period: lcm(width as u64, height as u64) as usize,
+            blizzards,
+        }
+    }
+
+    fn is_free(&self, point: Point2, time: usize) -> bool {
+        if point == self.start || point == self.goal {
+            return true;
+        }
+        if !self.blizzards.contains(point) {
+            return false;
+        }
+
+        let (width, height) = (self.blizzards.width as i32, self.blizzards.height as i32);
+        let t = time as i32;
+        let wrapped = |x: i32, y: i32| Point2::new(x.rem_euclid(width), y.rem_euclid(height));
+        self.blizzards[wrapped(point.x - t, point.y)] != b'>'
+            && self.blizzards[wrapped(point.x + t, point.y)] != b'<'
+            && self.blizzards[wrapped(point.x, point.y - t)] != b'v'
+            && self.blizzards[wrapped(point.x, point.y + t)] != b'^'
+    }
+
+    // Index of a position within one minute of the search space
+    fn index(&self, point: Point2) -> usize {
+        if point == self.start {
+            self.blizzards.width * self.blizzards.height
+        } else if point == self.goal {
+            self.blizzards.width * self.blizzards.height + 1
+        } else {
+            point.y as usize * self.blizzards.width + point.x as usize
+        }
+    }
+
+    // Breadth-first search over (position, time modulo the period), returns the
+    // time of arrival at `to` when leaving `from` at `departure`
+    fn crossing(&self, from: Point2, to: Point2, departure: usize) -> Option<usize> {
+        let positions = self.blizzards.width * self.blizzards.height + 2;
+        let mut seen = vec![false; self.period * positions];
+        let mut queue = VecDeque::from([(from, departure)]);
+        seen[departure % self.period * positions + self.index(from)] = true;
+
+        while let Some((position, time)) = queue.pop_front() {
+            if position == to {
+                return Some(time);
+            }
+
+            let time = time + 1;
+            let moves = Dir4::ALL.into_iter().map(|dir| position + dir);
+            for next in moves.chain([position]) {
+                if !self.is_free(next, time) {
+                    continue;
+                }
+                let state = time % self.period * positions + self.index(next);
+                if !seen[state] {
+                    seen[state] = true;
+                    queue.push_back((next, time));
+                }
+            }
+        }
+
+        None
+    }
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    None
+    let valley = Valley::parse(input);
+    valley
+        .crossing(valley.start, valley.goal, 0)
+        .map(|t| t as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    None
+    let valley = Valley::parse(input);
+    let there = valley.crossing(valley.start, valley.goal, 0)?;
+    let back = valley.crossing(valley.goal, valley.start, there)?;
+    let there_again = valley.crossing(valley.start, valley.goal, back)?;
+    Some(there_again as u32)
 }
 
 fn main() {
@@ -19,12 +122,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 24);
-        assert_eq!(part_one(&input), None);
+        assert_eq!(part_one(&input), Some(18));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 24);
-        assert_eq!(part_two(&input), None);
+        assert_eq!(part_two(&input), Some(54));
     }
 }
@@ -0,0 +1,35 @@
+use advent_of_code::helpers::Snafu;
+
+pub fn part_one(input: &str) -> Option<Snafu> {
+    input.lines().map(|line| line.parse::<Snafu>().ok()).sum()
+}
+
+pub fn part_two(_input: &str) -> Option<u32> {
+    None
+}
+
+fn main() {
+    let input = &advent_of_code::read_file("inputs", 25);
+    advent_of_code::solve!(1, part_one, input);
+    advent_of_code::solve!(2, part_two, input);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", 25);
+        assert_eq!(
+            part_one(&input).map(|n| n.to_string()),
+            Some("2=-1=0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", 25);
+        assert_eq!(part_two(&input), None);
+    }
+}
@@ -2,3 +2,1148 @@
  * Use this file if you want to extract helpers from your solutions.
  * Example import from this file: `use advent_of_code::helpers::example_fn;`.
  */
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, BitAnd, BitOr, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A cycle in a sequence of states: the state at step `start + length` is
+/// equivalent to the state at step `start`, and the sequence repeats from there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Maps any step onto the step before `start + length` that has an equivalent state.
+    pub fn equivalent_step(&self, step: usize) -> usize {
+        if step < self.start {
+            step
+        } else {
+            self.start + (step - self.start) % self.length
+        }
+    }
+
+    /// Extrapolates a metric to an arbitrary step, assuming the metric grows by a
+    /// constant amount every cycle. `metric` is only asked for steps in `0..=start + length`.
+    pub fn extrapolate(&self, step: usize, metric: impl Fn(usize) -> i64) -> i64 {
+        if step < self.start {
+            return metric(step);
+        }
+        let cycles = ((step - self.start) / self.length) as i64;
+        let growth = metric(self.start + self.length) - metric(self.start);
+        metric(self.equivalent_step(step)) + cycles * growth
+    }
+}
+
+/// Floyd's tortoise and hare. Only keeps two states around, but advances
+/// the sequence about three times as often as the hash map based detection.
+pub fn floyd<S, K: PartialEq>(initial: S, step: impl Fn(&S) -> S, key: impl Fn(&S) -> K) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while key(&tortoise) != key(&hare) {
+        tortoise = step(&tortoise);
+        hare = step(&step(&hare));
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while key(&tortoise) != key(&hare) {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while key(&tortoise) != key(&hare) {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Brent's algorithm. Same memory footprint as `floyd`, but fewer state transitions.
+pub fn brent<S, K: PartialEq>(initial: S, step: impl Fn(&S) -> S, key: impl Fn(&S) -> K) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = key(&initial);
+    let mut hare = step(&initial);
+    while tortoise != key(&hare) {
+        if power == length {
+            tortoise = key(&hare);
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut hare = (1..length).fold(step(&initial), |state, _| step(&state));
+    let mut tortoise = initial;
+    let mut start = 0;
+    while key(&tortoise) != key(&hare) {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Hash map based detection. Runs the sequence exactly until the first repeated key
+/// and returns every state seen so far, so metrics can be derived from the history.
+pub fn detect_cycle<S, K: Hash + Eq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    key: impl Fn(&S) -> K,
+) -> (Cycle, Vec<S>) {
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut history = vec![initial];
+    loop {
+        let current = history.len() - 1;
+        match seen.insert(key(&history[current]), current) {
+            Some(start) => {
+                let cycle = Cycle {
+                    start,
+                    length: current - start,
+                };
+                return (cycle, history);
+            }
+            None => {
+                let next = step(&history[current]);
+                history.push(next);
+            }
+        }
+    }
+}
+
+/// A point on a 2D plane. `y` grows downwards, like rows in the puzzle inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2 {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Self) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Clamps every component to -1, 0 or 1, e.g. to move a single step towards another point.
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn neighbours4(self) -> impl Iterator<Item = Point2> {
+        Dir4::ALL.into_iter().map(move |dir| self + dir)
+    }
+
+    pub fn neighbours8(self) -> impl Iterator<Item = Point2> {
+        Dir8::ALL.into_iter().map(move |dir| self + dir)
+    }
+}
+
+impl Add for Point2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<i32> for Point2 {
+    type Output = Self;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point2 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for Point2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Point2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Add<Dir4> for Point2 {
+    type Output = Self;
+
+    fn add(self, rhs: Dir4) -> Self::Output {
+        self + rhs.delta()
+    }
+}
+
+impl Add<Dir8> for Point2 {
+    type Output = Self;
+
+    fn add(self, rhs: Dir8) -> Self::Output {
+        self + rhs.delta()
+    }
+}
+
+impl AddAssign<Dir4> for Point2 {
+    fn add_assign(&mut self, rhs: Dir4) {
+        *self = *self + rhs;
+    }
+}
+
+impl AddAssign<Dir8> for Point2 {
+    fn add_assign(&mut self, rhs: Dir8) {
+        *self = *self + rhs;
+    }
+}
+
+impl From<(usize, usize)> for Point2 {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self::new(x as i32, y as i32)
+    }
+}
+
+impl TryFrom<Point2> for (usize, usize) {
+    type Error = String;
+
+    fn try_from(point: Point2) -> Result<Self, Self::Error> {
+        match (usize::try_from(point.x), usize::try_from(point.y)) {
+            (Ok(x), Ok(y)) => Ok((x, y)),
+            _ => Err(format!("Expected non-negative coordinates, got {point:?}")),
+        }
+    }
+}
+
+/// A point in 3D space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Self) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    /// The six points sharing a face with this one.
+    pub fn neighbours(self) -> impl Iterator<Item = Point3> {
+        [
+            Point3::new(1, 0, 0),
+            Point3::new(-1, 0, 0),
+            Point3::new(0, 1, 0),
+            Point3::new(0, -1, 0),
+            Point3::new(0, 0, 1),
+            Point3::new(0, 0, -1),
+        ]
+        .into_iter()
+        .map(move |delta| self + delta)
+    }
+}
+
+impl Add for Point3 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Mul<i32> for Point3 {
+    type Output = Self;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+/// The four orthogonal directions, in clockwise order starting at `Right`.
+/// The discriminant doubles as the puzzles' usual facing score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir4 {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+impl Dir4 {
+    pub const ALL: [Dir4; 4] = [Dir4::Right, Dir4::Down, Dir4::Left, Dir4::Up];
+
+    pub fn delta(self) -> Point2 {
+        match self {
+            Dir4::Right => Point2::new(1, 0),
+            Dir4::Down => Point2::new(0, 1),
+            Dir4::Left => Point2::new(-1, 0),
+            Dir4::Up => Point2::new(0, -1),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+impl TryFrom<char> for Dir4 {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'R' | '>' => Ok(Dir4::Right),
+            'D' | 'v' => Ok(Dir4::Down),
+            'L' | '<' => Ok(Dir4::Left),
+            'U' | '^' => Ok(Dir4::Up),
+            _ => Err(format!("Expected one of U, D, L, R, ^, v, <, >, got {c:?}")),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::Right => Dir8::E,
+            Dir4::Down => Dir8::S,
+            Dir4::Left => Dir8::W,
+            Dir4::Up => Dir8::N,
+        }
+    }
+}
+
+/// The eight compass directions, in clockwise order starting at `N`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    pub const ALL: [Dir8; 8] = [
+        Dir8::N,
+        Dir8::NE,
+        Dir8::E,
+        Dir8::SE,
+        Dir8::S,
+        Dir8::SW,
+        Dir8::W,
+        Dir8::NW,
+    ];
+
+    pub fn delta(self) -> Point2 {
+        match self {
+            Dir8::N => Point2::new(0, -1),
+            Dir8::NE => Point2::new(1, -1),
+            Dir8::E => Point2::new(1, 0),
+            Dir8::SE => Point2::new(1, 1),
+            Dir8::S => Point2::new(0, 1),
+            Dir8::SW => Point2::new(-1, 1),
+            Dir8::W => Point2::new(-1, 0),
+            Dir8::NW => Point2::new(-1, -1),
+        }
+    }
+
+    /// Turns by 45 degrees.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns by 45 degrees.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+}
+
+/// A dense, rectangular grid of cells, addressed by `Point2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses a grid from lines of text, mapping every byte to a cell.
+    /// All lines must have the same length.
+    pub fn parse<E: From<String>>(
+        input: &str,
+        mut cell: impl FnMut(u8) -> Result<T, E>,
+    ) -> Result<Self, E> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for line in input.lines() {
+            let expected = *width.get_or_insert(line.len());
+            if line.len() != expected {
+                return Err(format!(
+                    "Expected all lines to have length {expected}, line {} has length {}",
+                    height + 1,
+                    line.len()
+                )
+                .into());
+            }
+            height += 1;
+            for byte in line.bytes() {
+                cells.push(cell(byte)?);
+            }
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    fn index_of(&self, point: Point2) -> Option<usize> {
+        let (x, y) = <(usize, usize)>::try_from(point).ok()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    pub fn contains(&self, point: Point2) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    pub fn get(&self, point: Point2) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point2) -> Option<&mut T> {
+        self.index_of(point).map(|i| &mut self.cells[i])
+    }
+
+    /// All points of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point2> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point2::from((x, y))))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// Finds the first point (row by row) whose cell matches the predicate.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Point2> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(p, _)| p)
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point2) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} is out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, point: Point2) -> &mut Self::Output {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point:?} is out of bounds"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, cell) in self.cells.iter().enumerate() {
+            if i > 0 && i % self.width == 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{cell}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A cache for memoized recursion. Counts hits and misses, which helps when
+/// tuning the key of a search that is slower than expected.
+///
+/// ```
+/// use advent_of_code::helpers::Memo;
+///
+/// fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+///     memo.get_or_compute(n, |memo| if n < 2 { n } else { fib(memo, n - 1) + fib(memo, n - 2) })
+/// }
+///
+/// assert_eq!(fib(&mut Memo::new(), 10), 55);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the cached value for `key`, or computes and caches it.
+    /// `compute` gets the memo back, so it can recurse.
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A set of small integers in `0..64`, stored as the bits of a single `u64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct BitSet64(u64);
+
+impl BitSet64 {
+    pub const EMPTY: BitSet64 = BitSet64(0);
+
+    pub fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    pub fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Inserts a value, returning whether it was not present yet.
+    /// Panics for values of 64 and above.
+    pub fn insert(&mut self, value: u8) -> bool {
+        assert!(
+            value < 64,
+            "BitSet64 can only hold values below 64, got {value}"
+        );
+        let absent = !self.contains(value);
+        self.0 |= 1 << value;
+        absent
+    }
+
+    /// Removes a value, returning whether it was present.
+    pub fn remove(&mut self, value: u8) -> bool {
+        let present = self.contains(value);
+        if present {
+            self.0 &= !(1 << value);
+        }
+        present
+    }
+
+    pub fn contains(self, value: u8) -> bool {
+        value < 64 && self.0 & (1 << value) != 0
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    pub fn is_subset(self, other: Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn first(self) -> Option<u8> {
+        (!self.is_empty()).then(|| self.0.trailing_zeros() as u8)
+    }
+
+    /// Iterates the values in ascending order.
+    pub fn iter(self) -> impl Iterator<Item = u8> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            let value = BitSet64(bits).first()?;
+            bits &= bits - 1;
+            Some(value)
+        })
+    }
+}
+
+impl FromIterator<u8> for BitSet64 {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut set = Self::EMPTY;
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+impl BitAnd for BitSet64 {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl BitOr for BitSet64 {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+/// A set of ASCII letters, backed by a `BitSet64`.
+/// `a-z` are stored as `0-25` and `A-Z` as `26-51`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct LetterSet(BitSet64);
+
+impl LetterSet {
+    pub const EMPTY: LetterSet = LetterSet(BitSet64::EMPTY);
+
+    /// The position of a letter within the set, `None` for anything but ASCII letters.
+    pub fn index(letter: u8) -> Option<u8> {
+        match letter {
+            b'a'..=b'z' => Some(letter - b'a'),
+            b'A'..=b'Z' => Some(letter - b'A' + 26),
+            _ => None,
+        }
+    }
+
+    fn letter(index: u8) -> u8 {
+        match index {
+            0..=25 => b'a' + index,
+            _ => b'A' + index - 26,
+        }
+    }
+
+    /// Inserts a letter, returning whether it was not present yet.
+    /// Panics for anything but ASCII letters.
+    pub fn insert(&mut self, letter: u8) -> bool {
+        let index = Self::index(letter)
+            .unwrap_or_else(|| panic!("Expected an ASCII letter, got {:?}", letter as char));
+        self.0.insert(index)
+    }
+
+    pub fn remove(&mut self, letter: u8) -> bool {
+        Self::index(letter).is_some_and(|index| self.0.remove(index))
+    }
+
+    pub fn contains(self, letter: u8) -> bool {
+        Self::index(letter).is_some_and(|index| self.0.contains(index))
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub fn len(self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn first(self) -> Option<u8> {
+        self.0.first().map(Self::letter)
+    }
+
+    /// Iterates the letters, `a-z` first, then `A-Z`.
+    pub fn iter(self) -> impl Iterator<Item = u8> {
+        self.0.iter().map(Self::letter)
+    }
+}
+
+impl TryFrom<&[u8]> for LetterSet {
+    type Error = String;
+
+    fn try_from(letters: &[u8]) -> Result<Self, Self::Error> {
+        let mut set = Self::EMPTY;
+        for &letter in letters {
+            let index = Self::index(letter)
+                .ok_or_else(|| format!("Expected an ASCII letter, got {:?}", letter as char))?;
+            set.0.insert(index);
+        }
+        Ok(set)
+    }
+}
+
+impl TryFrom<&str> for LetterSet {
+    type Error = String;
+
+    fn try_from(letters: &str) -> Result<Self, Self::Error> {
+        Self::try_from(letters.as_bytes())
+    }
+}
+
+impl BitAnd for LetterSet {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl BitOr for LetterSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+pub fn lcm(a: u64, b: u64) -> u64 {
+    a / gcd(a, b) * b
+}
+
+/// A number in balanced base 5 ("SNAFU"), with digits from -2 to 2 written as `=-012`.
+/// Digits are stored least significant first, without leading zeros.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Snafu(Vec<i8>);
+
+impl Snafu {
+    fn trimmed(mut digits: Vec<i8>) -> Self {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        Self(digits)
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("Expected at least one digit".to_string());
+        }
+        let digits = s
+            .chars()
+            .rev()
+            .map(|c| match c {
+                '=' => Ok(-2),
+                '-' => Ok(-1),
+                '0' => Ok(0),
+                '1' => Ok(1),
+                '2' => Ok(2),
+                _ => Err(format!("Expected one of =-012, got {c:?}")),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self::trimmed(digits))
+    }
+}
+
+impl Display for Snafu {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "0");
+        }
+        for digit in self.0.iter().rev() {
+            let c = match digit {
+                -2 => '=',
+                -1 => '-',
+                0 => '0',
+                1 => '1',
+                2 => '2',
+                _ => unreachable!(),
+            };
+            write!(f, "{c}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Adds digit by digit, carrying whenever a digit leaves the -2..=2 range
+impl Add for &Snafu {
+    type Output = Snafu;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let len = self.0.len().max(rhs.0.len());
+        let mut digits = Vec::with_capacity(len + 1);
+        let mut carry = 0;
+        for i in 0..len {
+            let mut digit = self.0.get(i).unwrap_or(&0) + rhs.0.get(i).unwrap_or(&0) + carry;
+            carry = 0;
+            if digit > 2 {
+                digit -= 5;
+                carry = 1;
+            } else if digit < -2 {
+                digit += 5;
+                carry = -1;
+            }
+            digits.push(digit);
+        }
+        digits.push(carry);
+        Snafu::trimmed(digits)
+    }
+}
+
+impl Add for Snafu {
+    type Output = Snafu;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Snafu::default(), |sum, n| sum + n)
+    }
+}
+
+impl From<i64> for Snafu {
+    fn from(value: i64) -> Self {
+        // i128 leaves room for the carry at the edges of the i64 range
+        let mut rest = value as i128;
+        let mut digits = vec![];
+        while rest != 0 {
+            let digit = match rest.rem_euclid(5) {
+                d @ 0..=2 => d,
+                d => d - 5,
+            };
+            digits.push(digit as i8);
+            rest = (rest - digit) / 5;
+        }
+        Self(digits)
+    }
+}
+
+impl TryFrom<&Snafu> for i64 {
+    type Error = String;
+
+    fn try_from(snafu: &Snafu) -> Result<Self, Self::Error> {
+        let value = snafu
+            .0
+            .iter()
+            .rev()
+            .try_fold(0_i128, |acc, &digit| {
+                acc.checked_mul(5)?.checked_add(digit as i128)
+            })
+            .and_then(|value| i64::try_from(value).ok());
+        value.ok_or_else(|| format!("{snafu} does not fit into an i64"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, 2, 3, 4, 5, 2, 3, 4, 5, ...
+    fn next(n: &u32) -> u32 {
+        if *n == 5 {
+            2
+        } else {
+            n + 1
+        }
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        let expected = Cycle {
+            start: 2,
+            length: 4,
+        };
+        assert_eq!(floyd(0, next, |&n| n), expected);
+        assert_eq!(brent(0, next, |&n| n), expected);
+
+        let (cycle, history) = detect_cycle(0, next, |&n| n);
+        assert_eq!(cycle, expected);
+        assert_eq!(history, vec![0, 1, 2, 3, 4, 5, 2]);
+    }
+
+    #[test]
+    fn test_cycle_extrapolation() {
+        // running sum of the sequence above
+        let (cycle, history) = detect_cycle(
+            (0_u32, 0_i64),
+            |&(n, sum)| (next(&n), sum + n as i64),
+            |&(n, _)| n,
+        );
+        let brute_force = (0..1000).fold((0_u32, 0_i64), |(n, sum), _| (next(&n), sum + n as i64));
+        assert_eq!(
+            cycle.extrapolate(1000, |step| history[step].1),
+            brute_force.1
+        );
+        assert_eq!(cycle.equivalent_step(1000), 4);
+    }
+
+    #[test]
+    fn test_points_and_directions() {
+        let p = Point2::new(3, -2);
+        assert_eq!(p + Dir4::Up, Point2::new(3, -3));
+        assert_eq!(p - Point2::new(1, 1), Point2::new(2, -3));
+        assert_eq!((p * 4).signum(), Point2::new(1, -1));
+        assert_eq!(p.manhattan(Point2::ORIGIN), 5);
+        assert_eq!(p.neighbours8().count(), 8);
+
+        assert_eq!(Dir4::try_from('U'), Dir4::try_from('^'));
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Right.turn_left(), Dir4::Up);
+        assert_eq!(Dir4::Left.reverse(), Dir4::Right);
+        assert_eq!(Dir8::NW.turn_right(), Dir8::N);
+        assert_eq!(Dir8::from(Dir4::Down).delta(), Dir4::Down.delta());
+        assert!(Dir4::try_from('x').is_err());
+
+        let q = Point3::new(1, 2, 3);
+        assert_eq!(q - q * 2, -q);
+        assert_eq!(q.manhattan(Point3::ORIGIN), 6);
+        assert!(q.neighbours().all(|n| n.manhattan(q) == 1));
+    }
+
+    #[test]
+    fn test_grid() {
+        let mut grid = Grid::parse("ab\ncd\nef", |b| Ok::<_, String>(b as char)).unwrap();
+        assert_eq!((grid.width, grid.height), (2, 3));
+        assert_eq!(grid[Point2::new(1, 2)], 'f');
+        assert_eq!(grid.get(Point2::new(2, 0)), None);
+        assert_eq!(grid.get(Point2::new(0, -1)), None);
+        assert_eq!(grid.position(|&c| c == 'd'), Some(Point2::new(1, 1)));
+
+        grid[Point2::ORIGIN] = 'z';
+        assert_eq!(grid.to_string(), "zb\ncd\nef");
+
+        assert!(Grid::parse("abc\nd\nef", Ok::<_, String>).is_err());
+    }
+
+    #[test]
+    fn test_memo() {
+        fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+            memo.get_or_compute(n, |memo| {
+                if n < 2 {
+                    n
+                } else {
+                    fib(memo, n - 1) + fib(memo, n - 2)
+                }
+            })
+        }
+
+        let mut memo = Memo::new();
+        assert_eq!(fib(&mut memo, 90), 2880067194370816120);
+        assert_eq!(memo.len(), 91);
+        assert_eq!(memo.misses(), 91);
+        assert_eq!(memo.hits(), 88);
+    }
+
+    #[test]
+    fn test_bitset() {
+        let mut set: BitSet64 = [1, 5, 63].into_iter().collect();
+        assert!(set.insert(7));
+        assert!(!set.insert(7));
+        assert!(set.remove(5));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1, 7, 63]);
+        assert_eq!((set & BitSet64::from_bits(0b10)).len(), 1);
+        assert_eq!((set | BitSet64::from_bits(0b101)).first(), Some(0));
+        assert!(!set.contains(64));
+        assert!(!set.remove(64));
+    }
+
+    #[test]
+    #[should_panic(expected = "below 64")]
+    fn test_bitset_insert_out_of_range() {
+        BitSet64::default().insert(64);
+    }
+
+    #[test]
+    fn test_letter_set() {
+        let a = LetterSet::try_from("vJrwpWtwJgWr").unwrap();
+        let b = LetterSet::try_from("hcsFMMfFFhFp").unwrap();
+        assert_eq!((a & b).iter().collect::<Vec<_>>(), vec![b'p']);
+        assert_eq!((a | b).len(), 14);
+        assert_eq!(LetterSet::index(b'Z'), Some(51));
+        assert!(a.contains(b'J') && !a.contains(b'j') && !a.contains(b'1'));
+        assert!(LetterSet::try_from("ab1").is_err());
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!([23, 19, 13, 17].into_iter().fold(1, lcm), 96577);
+    }
+
+    #[test]
+    fn test_snafu_conversion() {
+        for (decimal, snafu) in [
+            (0, "0"),
+            (3, "1="),
+            (2022, "1=11-2"),
+            (314159265, "1121-1110-1=0"),
+        ] {
+            assert_eq!(Snafu::from(decimal).to_string(), snafu);
+            assert_eq!(i64::try_from(&Snafu::from_str(snafu).unwrap()), Ok(decimal));
+        }
+
+        for value in [i64::MIN, i64::MIN + 1, -1, 1, i64::MAX - 1, i64::MAX] {
+            let snafu = Snafu::from(value);
+            assert_eq!(i64::try_from(&snafu), Ok(value));
+            assert_eq!(Snafu::from_str(&snafu.to_string()), Ok(snafu));
+        }
+        assert!(i64::try_from(&(Snafu::from(i64::MAX) + Snafu::from(1))).is_err());
+        assert!(Snafu::from_str("").is_err());
+        assert!(Snafu::from_str("1=3").is_err());
+    }
+
+    #[test]
+    fn test_snafu_addition() {
+        for (a, b) in [
+            (0, 0),
+            (2, 3),
+            (-7, 12),
+            (4890, -4890),
+            (123456789, 987654321),
+        ] {
+            assert_eq!(Snafu::from(a) + Snafu::from(b), Snafu::from(a + b));
+        }
+    }
+}